@@ -10,10 +10,17 @@ use self::wtftw::layout::LayoutMessage;
 use self::wtftw::window_system::Rectangle;
 use self::wtftw::window_system::Window;
 use self::wtftw::window_system::WindowSystem;
+use super::layout_info::LayoutInfo;
+use super::message_util::same_message;
+use super::strut_cache::StrutCache;
+use super::work_area::{dock_struts, root_rect, work_area};
 use std::collections::BTreeSet;
 
+/// The space a dock reserves at an edge of the root window: the
+/// edge, the distance from the edge, and the first and last pixel
+/// of the range along the edge.
 #[derive(Clone, Copy)]
-pub struct Strut(Direction, u64, u64, u64);
+pub struct Strut(pub Direction, pub u64, pub u64, pub u64);
 
 fn parse_strut_partial(x: Vec<u64>) -> Vec<Strut> {
     if x.len() != 12 {
@@ -42,28 +49,28 @@ fn parse_strut_partial(x: Vec<u64>) -> Vec<Strut> {
     //}
 }
 
-pub fn get_strut(window_system: &dyn WindowSystem, window: Window) -> Vec<Strut> {
-    let partial_strut = window_system.get_partial_strut(window);
+fn parse_strut(x: Vec<u64>) -> Vec<Strut> {
+    if x.len() != 4 {
+        return Vec::new();
+    }
 
-    fn parse_strut(x: Vec<u64>) -> Vec<Strut> {
-        if x.len() != 4 {
-            return Vec::new();
-        }
+    let s = vec![Bounded::min_value(), Bounded::max_value()];
+    let r: Vec<u64> = x.iter().chain(s.iter().cycle()).take(12).copied().collect();
+    parse_strut_partial(r)
 
-        let s = vec![Bounded::min_value(), Bounded::max_value()];
-        let r: Vec<u64> = x.iter().chain(s.iter().cycle()).take(12).copied().collect();
-        parse_strut_partial(r)
+    //match &x[..] {
+    //[a, b, c, d] => {
+    //let t = vec!(a, b, c, d);
+    //let s = vec!(Bounded::min_value(), Bounded::max_value());
+    //let r : Vec<u64> = t.iter().chain(s.iter().cycle()).take(12).map(|&x| x).collect();
+    //parse_strut_partial(r)
+    //}
+    //_ => Vec::new()
+    //}
+}
 
-        //match &x[..] {
-        //[a, b, c, d] => {
-        //let t = vec!(a, b, c, d);
-        //let s = vec!(Bounded::min_value(), Bounded::max_value());
-        //let r : Vec<u64> = t.iter().chain(s.iter().cycle()).take(12).map(|&x| x).collect();
-        //parse_strut_partial(r)
-        //}
-        //_ => Vec::new()
-        //}
-    }
+pub fn get_strut(window_system: &dyn WindowSystem, window: Window) -> Vec<Strut> {
+    let partial_strut = window_system.get_partial_strut(window);
 
     match partial_strut {
         Some(ps) => parse_strut_partial(ps),
@@ -77,10 +84,92 @@ pub fn get_strut(window_system: &dyn WindowSystem, window: Window) -> Vec<Strut>
     }
 }
 
+/// The struts of a dock window. A legacy `_NET_WM_STRUT` covers the
+/// whole edge of the root window, so it is limited to the part of
+/// the edge the dock itself spans. Otherwise a dock on one monitor
+/// would reserve space on all monitors next to it.
+pub fn get_dock_strut(window_system: &dyn WindowSystem, window: Window) -> Vec<Strut> {
+    if let Some(ps) = window_system.get_partial_strut(window) {
+        return parse_strut_partial(ps);
+    }
+
+    let Rectangle(x, y, w, h) = window_system.get_geometry(window);
+    let span = |start: i32, length: u32| {
+        let end = start as i64 + length as i64 - 1;
+        (start.max(0) as u64, end.max(0) as u64)
+    };
+    window_system
+        .get_strut(window)
+        .map_or_else(Vec::new, parse_strut)
+        .into_iter()
+        .map(|Strut(d, n, _, _)| {
+            let (start, end) = match d {
+                Direction::Left | Direction::Right => span(y, h),
+                Direction::Up | Direction::Down => span(x, w),
+            };
+            Strut(d, n, start, end)
+        })
+        .collect()
+}
+
+/// Space reserved at an edge of every screen without a dock window.
+/// Like struts, reservations are measured from the screen edge and
+/// only the largest strut or reservation at an edge counts.
+#[derive(Clone, Copy)]
+pub enum Reservation {
+    /// A fixed number of pixels
+    Pixels(Direction, u32),
+    /// A fraction of the screen's width or height
+    Fraction(Direction, f32),
+}
+
+impl Reservation {
+    /// The strut reserving the same space on the given screen
+    fn to_strut(self, screen: Rectangle, root: Rectangle) -> Strut {
+        let Rectangle(x, y, w, h) = screen;
+        let Rectangle(_, _, rw, rh) = root;
+        let (d, size) = match self {
+            Reservation::Pixels(d, p) => (d, p as u64),
+            Reservation::Fraction(d, f) => {
+                let extent = match d {
+                    Direction::Left | Direction::Right => w,
+                    Direction::Up | Direction::Down => h,
+                };
+                (d, (extent as f32 * f).round().max(0.0) as u64)
+            }
+        };
+
+        let (x, y, w, h) = (x as i64, y as i64, w as i64, h as i64);
+        let (n, start, end) = match d {
+            Direction::Left => (x + size as i64, y, y + h - 1),
+            Direction::Right => (rw as i64 - (x + w) + size as i64, y, y + h - 1),
+            Direction::Up => (y + size as i64, x, x + w - 1),
+            Direction::Down => (rh as i64 - (y + h) + size as i64, x, x + w - 1),
+        };
+        Strut(d, n.max(0) as u64, start.max(0) as u64, end.max(0) as u64)
+    }
+}
+
+/// The struts a message of an AvoidStrutsLayout toggles
+#[derive(Clone, Copy)]
+pub enum StrutToggle {
+    /// Ignore all struts, or avoid the configured ones again
+    All,
+    /// Ignore the struts at the given screen edge, or avoid them again
+    Side(Direction),
+}
+
 /// A layout that avoids dock like windows (e.g. dzen, xmobar, ...)
 /// to not overlap them.
+///
+/// Without a StrutCache, all windows are checked for struts on
+/// every layout pass.
 pub struct AvoidStrutsLayout {
     directions: BTreeSet<Direction>,
+    configured: BTreeSet<Direction>,
+    pub toggles: Vec<(LayoutMessage, StrutToggle)>,
+    pub reservations: Vec<Reservation>,
+    cache: Option<StrutCache>,
     layout: Box<dyn Layout>,
 }
 
@@ -88,10 +177,88 @@ impl AvoidStrutsLayout {
     /// Create a new AvoidStrutsLayout, containing the given layout
     /// and avoiding struts in the given directions.
     pub fn boxed_new(d: Vec<Direction>, layout: Box<dyn Layout>) -> Box<dyn Layout> {
-        Box::new(AvoidStrutsLayout {
-            directions: d.iter().copied().collect(),
+        AvoidStrutsLayout::new(d, layout).boxed()
+    }
+
+    /// Create a new AvoidStrutsLayout to be configured further
+    /// before boxing it, e.g. with toggles or a StrutCache
+    pub fn new(d: Vec<Direction>, layout: Box<dyn Layout>) -> AvoidStrutsLayout {
+        let directions: BTreeSet<Direction> = d.iter().copied().collect();
+        AvoidStrutsLayout {
+            configured: directions.clone(),
+            directions,
+            toggles: Vec::new(),
+            reservations: Vec::new(),
+            cache: None,
             layout: layout.copy(),
-        })
+        }
+    }
+
+    /// Toggle avoiding the struts when receiving the associated
+    /// message, e.g. to hide the bars for a presentation
+    pub fn with_toggles(mut self, toggles: Vec<(LayoutMessage, StrutToggle)>) -> AvoidStrutsLayout {
+        self.toggles = toggles;
+        self
+    }
+
+    /// Take the struts from the given cache instead of querying
+    /// all windows every time
+    pub fn with_cache(mut self, cache: StrutCache) -> AvoidStrutsLayout {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Reserve space at the screen edges in addition to the struts
+    /// of the docks, for programs like conky or trays that don't
+    /// set any struts. Reservations are toggled like struts.
+    pub fn with_reservations(mut self, reservations: Vec<Reservation>) -> AvoidStrutsLayout {
+        self.reservations = reservations;
+        self
+    }
+
+    pub fn boxed(self) -> Box<dyn Layout> {
+        Box::new(self)
+    }
+
+    /// The struts of all dock windows
+    fn struts(&self, window_system: &dyn WindowSystem) -> Vec<Strut> {
+        match self.cache {
+            Some(ref cache) => cache.struts(window_system),
+            None => dock_struts(window_system),
+        }
+    }
+
+    /// Whether struts at the given screen edge are avoided
+    pub fn is_avoiding(&self, direction: Direction) -> bool {
+        self.directions.contains(&direction)
+    }
+
+    pub fn toggle(&mut self, toggle: StrutToggle) {
+        match toggle {
+            StrutToggle::All if self.directions.is_empty() => {
+                self.directions = self.configured.clone();
+            }
+            StrutToggle::All => self.directions.clear(),
+            StrutToggle::Side(d) => {
+                if !self.directions.remove(&d) {
+                    self.directions.insert(d);
+                }
+            }
+        }
+    }
+
+    /// The structured description of the layout and its children
+    pub fn info(&self) -> LayoutInfo {
+        self.directions
+            .iter()
+            .map(|d| match *d {
+                Direction::Up => "Up",
+                Direction::Down => "Down",
+                Direction::Left => "Left",
+                Direction::Right => "Right",
+            })
+            .fold(LayoutInfo::new("Struts"), |info, d| info.with_parameter(d))
+            .with_child(&*self.layout)
     }
 }
 
@@ -104,23 +271,15 @@ impl Layout for AvoidStrutsLayout {
         stack: &Option<Stack<Window>>,
     ) -> Vec<(Window, Rectangle)> {
         let new_screen = stack.clone().map_or(screen, |_| {
-            window_system
-                .get_windows()
+            let root = root_rect(window_system, screen);
+            let reserved = self.reservations.iter().map(|r| r.to_strut(screen, root));
+            let struts: Vec<Strut> = self
+                .struts(window_system)
                 .into_iter()
-                .filter(|&w| {
-                    window_system.is_dock(w) && window_system.get_geometry(w).overlaps(&screen)
-                })
-                .flat_map(|x| get_strut(window_system, x).into_iter())
+                .chain(reserved)
                 .filter(|&Strut(s, _, _, _)| self.directions.contains(&s))
-                .fold(screen, |Rectangle(x, y, w, h), Strut(d, sw, _, _)| {
-                    let s = sw as u32;
-                    match d {
-                        Direction::Up => Rectangle(x, y + s as i32, w, h - s),
-                        Direction::Down => Rectangle(x, y, w, h - s),
-                        Direction::Left => Rectangle(x + s as i32, y, w - s, h),
-                        Direction::Right => Rectangle(x, y, w - s, h),
-                    }
-                })
+                .collect();
+            work_area(screen, root, &struts)
         });
 
         self.layout
@@ -134,17 +293,31 @@ impl Layout for AvoidStrutsLayout {
         stack: &Option<Stack<Window>>,
         config: &GeneralConfig,
     ) -> bool {
+        let toggle = self
+            .toggles
+            .iter()
+            .find(|&&(m, _)| same_message(m, message))
+            .map(|&(_, t)| t);
+        if let Some(t) = toggle {
+            self.toggle(t);
+            return true;
+        }
+
         self.layout
             .apply_message(message, window_system, stack, config)
     }
 
     fn description(&self) -> String {
-        self.layout.description()
+        self.info().to_string()
     }
 
     fn copy(&self) -> Box<dyn Layout> {
         Box::new(AvoidStrutsLayout {
             directions: self.directions.clone(),
+            configured: self.configured.clone(),
+            toggles: self.toggles.clone(),
+            reservations: self.reservations.clone(),
+            cache: self.cache.clone(),
             layout: self.layout.copy(),
         })
     }
@@ -458,7 +458,7 @@ impl BinarySpacePartition {
         }
     }
 
-    fn rectangles(&self, rect: Rectangle) -> Vec<Rectangle> {
+    pub(crate) fn rectangles(&self, rect: Rectangle) -> Vec<Rectangle> {
         self.tree.clone().map_or(Vec::new(), |t| match t {
             Tree::Leaf => vec![rect],
             Tree::Node(value, l, r) => {
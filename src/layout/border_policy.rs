@@ -0,0 +1,145 @@
+extern crate wtftw;
+
+use self::wtftw::config::GeneralConfig;
+use self::wtftw::layout::LayoutMessage;
+use self::wtftw::window_system::Window;
+use self::wtftw::window_system::WindowSystem;
+use std::rc::Rc;
+
+/// Asks the window manager about a window, e.g. whether it has
+/// the urgency hint set
+pub type WindowQuery = Rc<dyn Fn(&dyn WindowSystem, Window) -> bool>;
+
+/// A state of a window that a BorderPolicy can style
+#[derive(Clone)]
+pub enum WindowState {
+    /// The window with the input focus. Only one window has the
+    /// input focus at a time, so this also tells which screen is
+    /// focused.
+    Focused,
+    /// Windows the query reports as urgent
+    Urgent(WindowQuery),
+    /// Windows the query reports as floating
+    Floating(WindowQuery),
+    /// The only window of the contained layout
+    Only,
+    /// Windows marked with the mark message of the policy
+    Marked,
+}
+
+/// The width and colour of a border. Unset values are taken from
+/// the next matching rule, or the defaults if no other rule sets them.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BorderStyle {
+    pub width: Option<u32>,
+    pub color: Option<u32>,
+}
+
+impl BorderStyle {
+    pub fn new(width: u32, color: u32) -> BorderStyle {
+        BorderStyle {
+            width: Some(width),
+            color: Some(color),
+        }
+    }
+
+    pub fn width(width: u32) -> BorderStyle {
+        BorderStyle {
+            width: Some(width),
+            color: None,
+        }
+    }
+
+    pub fn color(color: u32) -> BorderStyle {
+        BorderStyle {
+            width: None,
+            color: Some(color),
+        }
+    }
+}
+
+/// What a BorderPolicy knows about a window while it is laid out
+pub struct WindowContext {
+    pub window: Window,
+    pub focused: Window,
+    pub only: bool,
+    pub marked: bool,
+}
+
+impl WindowState {
+    pub fn matches(&self, window_system: &dyn WindowSystem, context: &WindowContext) -> bool {
+        match *self {
+            WindowState::Focused => context.window == context.focused,
+            WindowState::Urgent(ref query) => query(window_system, context.window),
+            WindowState::Floating(ref query) => query(window_system, context.window),
+            WindowState::Only => context.only,
+            WindowState::Marked => context.marked,
+        }
+    }
+}
+
+/// Decides the border of every window depending on its state.
+///
+/// The rules are checked in order and the first matching rule
+/// that sets the width or the colour wins. Windows without a
+/// matching rule get the default width of the WithBordersLayout
+/// and the colours from the `GeneralConfig`.
+#[derive(Clone, Default)]
+pub struct BorderPolicy {
+    pub rules: Vec<(WindowState, BorderStyle)>,
+    /// The message that marks the focused window or unmarks it again
+    pub mark: Option<LayoutMessage>,
+}
+
+impl BorderPolicy {
+    pub fn new() -> BorderPolicy {
+        BorderPolicy::default()
+    }
+
+    pub fn with_rule(mut self, state: WindowState, style: BorderStyle) -> BorderPolicy {
+        self.rules.push((state, style));
+        self
+    }
+
+    pub fn with_mark(mut self, message: LayoutMessage) -> BorderPolicy {
+        self.mark = Some(message);
+        self
+    }
+
+    /// Whether the policy changes the border colours at all
+    pub fn sets_colors(&self) -> bool {
+        self.rules.iter().any(|(_, style)| style.color.is_some())
+    }
+
+    /// The width and colour of the window's border
+    pub fn border(
+        &self,
+        window_system: &dyn WindowSystem,
+        context: &WindowContext,
+        width: u32,
+        config: &GeneralConfig,
+    ) -> (u32, u32) {
+        let matching: Vec<BorderStyle> = self
+            .rules
+            .iter()
+            .filter(|(state, _)| state.matches(window_system, context))
+            .map(|&(_, style)| style)
+            .collect();
+
+        let width = matching.iter().find_map(|s| s.width).unwrap_or(width);
+        let color = matching
+            .iter()
+            .find_map(|s| s.color)
+            .unwrap_or_else(|| default_color(context.window, context.focused, config));
+        (width, color)
+    }
+}
+
+/// The colour the window manager gives a window's border
+pub fn default_color(window: Window, focused: Window, config: &GeneralConfig) -> u32 {
+    if window == focused {
+        config.focus_border_color
+    } else {
+        config.border_color
+    }
+}
@@ -0,0 +1,131 @@
+extern crate wtftw;
+
+use self::wtftw::config::GeneralConfig;
+use self::wtftw::core::stack::Stack;
+use self::wtftw::layout::Layout;
+use self::wtftw::layout::LayoutMessage;
+use self::wtftw::window_system::Rectangle;
+use self::wtftw::window_system::Window;
+use self::wtftw::window_system::WindowSystem;
+use std::borrow::ToOwned;
+
+/// A layout that cascades all windows diagonally across the
+/// screen, each one offset from the previous one by a fraction
+/// of the screen size (like Roledex in xmonad).
+///
+/// The focused window comes first, so it ends up on top. The
+/// other windows follow from the bottom right to the top left,
+/// so every window leaves the title bar of the next one visible.
+/// `Increase` and `Decrease` change the size of the windows.
+#[derive(Clone, Copy)]
+pub struct CascadeLayout {
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub increment_ratio: f32,
+}
+
+impl CascadeLayout {
+    pub fn boxed_new() -> Box<dyn Layout> {
+        CascadeLayout::boxed_with(0.03, 0.03, 0.75, 0.75)
+    }
+
+    /// Create a new CascadeLayout with the given offset
+    /// and window size as fractions of the screen. The window
+    /// size is limited to the size of the screen.
+    pub fn boxed_with(offset_x: f32, offset_y: f32, width: f32, height: f32) -> Box<dyn Layout> {
+        Box::new(CascadeLayout {
+            offset_x,
+            offset_y,
+            width: width.clamp(0.01, 1.0),
+            height: height.clamp(0.01, 1.0),
+            increment_ratio: 0.03,
+        })
+    }
+
+    fn cascade(&self, Rectangle(sx, sy, sw, sh): Rectangle, n: usize) -> Vec<Rectangle> {
+        let w = (sw as f32 * self.width) as u32;
+        let h = (sh as f32 * self.height) as u32;
+        let dx = (sw as f32 * self.offset_x) as u32;
+        let dy = (sh as f32 * self.offset_y) as u32;
+
+        // Start over in the top left corner once
+        // the windows would leave the screen
+        let fit_x = sw
+            .saturating_sub(w)
+            .checked_div(dx)
+            .map_or(n, |f| f as usize + 1);
+        let fit_y = sh
+            .saturating_sub(h)
+            .checked_div(dy)
+            .map_or(n, |f| f as usize + 1);
+        let fit = fit_x.min(fit_y).max(1);
+
+        (0..n)
+            .map(|i| {
+                let i = (i % fit) as u32;
+                Rectangle(sx + (i * dx) as i32, sy + (i * dy) as i32, w, h)
+            })
+            .collect()
+    }
+}
+
+impl Layout for CascadeLayout {
+    fn apply_layout(
+        &mut self,
+        _: &dyn WindowSystem,
+        screen: Rectangle,
+        _: &GeneralConfig,
+        stack: &Option<Stack<Window>>,
+    ) -> Vec<(Window, Rectangle)> {
+        match *stack {
+            Some(ref s) => {
+                let ws = s.integrate();
+                let rs = self.cascade(screen, ws.len());
+                let cascade: Vec<(Window, Rectangle)> = ws.into_iter().zip(rs).collect();
+
+                cascade
+                    .iter()
+                    .filter(|&&(w, _)| w == s.focus)
+                    .chain(cascade.iter().rev().filter(|&&(w, _)| w != s.focus))
+                    .copied()
+                    .collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    fn apply_message(
+        &mut self,
+        message: LayoutMessage,
+        _: &dyn WindowSystem,
+        _: &Option<Stack<Window>>,
+        _: &GeneralConfig,
+    ) -> bool {
+        let d = self.increment_ratio;
+        match message {
+            LayoutMessage::Increase => {
+                self.width = (self.width + d).min(1.0);
+                self.height = (self.height + d).min(1.0);
+                true
+            }
+            LayoutMessage::Decrease => {
+                if self.width - d > 0.0 && self.height - d > 0.0 {
+                    self.width -= d;
+                    self.height -= d;
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn description(&self) -> String {
+        "Cascade".to_owned()
+    }
+
+    fn copy(&self) -> Box<dyn Layout> {
+        Box::new(*self)
+    }
+}
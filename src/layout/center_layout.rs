@@ -7,7 +7,7 @@ use self::wtftw::layout::LayoutMessage;
 use self::wtftw::window_system::Rectangle;
 use self::wtftw::window_system::Window;
 use self::wtftw::window_system::WindowSystem;
-use std::borrow::ToOwned;
+use super::layout_info::LayoutInfo;
 
 pub struct CenterLayout {
     pub layout: Box<dyn Layout>,
@@ -19,6 +19,11 @@ impl CenterLayout {
             layout: layout.copy(),
         })
     }
+
+    /// The structured description of the layout and its children
+    pub fn info(&self) -> LayoutInfo {
+        LayoutInfo::new("Center").with_child(&*self.layout)
+    }
 }
 
 impl Layout for CenterLayout {
@@ -80,7 +85,7 @@ impl Layout for CenterLayout {
     }
 
     fn description(&self) -> String {
-        "Center".to_owned()
+        self.info().to_string()
     }
 
     fn copy(&self) -> Box<dyn Layout> {
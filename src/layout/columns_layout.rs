@@ -0,0 +1,248 @@
+extern crate wtftw;
+
+use self::wtftw::config::GeneralConfig;
+use self::wtftw::core::stack::Stack;
+use self::wtftw::layout::Layout;
+use self::wtftw::layout::LayoutMessage;
+use self::wtftw::window_system::Rectangle;
+use self::wtftw::window_system::Window;
+use self::wtftw::window_system::WindowSystem;
+use std::borrow::ToOwned;
+
+/// A layout that splits the screen into columns of
+/// resizable width, with the windows of each column
+/// stacked vertically. Windows remember the column
+/// they are in. Every column holds up to a given number
+/// of windows and new windows are put into the leftmost
+/// column with room left, or into the column holding
+/// the fewest windows once all columns are full.
+///
+/// `Increase` and `Decrease` change the width of the
+/// focused column, `IncreaseMaster` and `DecreaseMaster`
+/// add or remove a column and `IncreaseSlave` and
+/// `DecreaseSlave` move the focused window into the
+/// next or previous column.
+#[derive(Clone)]
+pub struct ColumnsLayout {
+    pub increment_ratio: f32,
+    pub widths: Vec<f32>,
+    pub capacities: Vec<usize>,
+    columns: Vec<Vec<Window>>,
+}
+
+impl ColumnsLayout {
+    /// Create a new ColumnsLayout with equally wide columns
+    /// that hold the given number of windows each, e.g.
+    /// `vec![1, 3, 3]` for a single master window and two
+    /// columns of three windows next to it
+    pub fn boxed_new(capacities: Vec<usize>) -> Box<dyn Layout> {
+        let capacities = if capacities.is_empty() {
+            vec![1]
+        } else {
+            capacities.into_iter().map(|c| c.max(1)).collect()
+        };
+        let n = capacities.len();
+        Box::new(ColumnsLayout {
+            increment_ratio: 0.1,
+            widths: vec![1.0; n],
+            capacities,
+            columns: vec![Vec::new(); n],
+        })
+    }
+
+    /// Index of the column that contains the given window
+    pub fn column_of(&self, window: Window) -> Option<usize> {
+        self.columns.iter().position(|c| c.contains(&window))
+    }
+
+    /// Number of windows in each column
+    pub fn column_sizes(&self) -> Vec<usize> {
+        self.columns.iter().map(|c| c.len()).collect()
+    }
+
+    /// Move the given window by `offset` columns. Does nothing
+    /// if the window would end up outside of the columns.
+    pub fn move_window(&mut self, window: Window, offset: i32) {
+        if let Some(from) = self.column_of(window) {
+            let to = from as i32 + offset;
+            if to < 0 || to as usize >= self.columns.len() {
+                return;
+            }
+            self.columns[from].retain(|&w| w != window);
+            self.columns[to as usize].push(window);
+        }
+    }
+
+    /// Add a new empty column on the right, holding as
+    /// many windows as the rightmost column
+    pub fn add_column(&mut self) {
+        let capacity = self.capacities.last().copied().unwrap_or(1);
+        self.columns.push(Vec::new());
+        self.widths.push(1.0);
+        self.capacities.push(capacity);
+    }
+
+    /// Remove the rightmost column, moving its windows
+    /// into its left neighbour
+    pub fn remove_column(&mut self) {
+        if self.columns.len() < 2 {
+            return;
+        }
+
+        let last = self.columns.pop().unwrap_or_default();
+        self.widths.pop();
+        self.capacities.pop();
+        if let Some(c) = self.columns.last_mut() {
+            c.extend(last);
+        }
+    }
+
+    fn resize_column(&mut self, column: usize, d: f32) {
+        if let Some(w) = self.widths.get_mut(column) {
+            if *w + d > 0.0 {
+                *w += d;
+            }
+        }
+    }
+
+    /// The column a new window is put into: the leftmost
+    /// column that isn't full yet, or the one with the
+    /// fewest windows if all of them are
+    fn column_for_new_window(&self) -> usize {
+        let free = self
+            .columns
+            .iter()
+            .zip(self.capacities.iter())
+            .position(|(c, &capacity)| c.len() < capacity);
+        free.unwrap_or_else(|| {
+            let (index, _) = self
+                .columns
+                .iter()
+                .enumerate()
+                .min_by_key(|&(i, c)| (c.len(), i))
+                .unwrap();
+            index
+        })
+    }
+
+    /// Forget windows that are gone and put new windows
+    /// into the columns according to their capacities
+    fn update(&mut self, windows: &[Window]) {
+        for column in self.columns.iter_mut() {
+            column.retain(|w| windows.contains(w));
+        }
+
+        for &window in windows {
+            if self.column_of(window).is_none() {
+                let index = self.column_for_new_window();
+                self.columns[index].push(window);
+            }
+        }
+    }
+}
+
+impl Layout for ColumnsLayout {
+    fn apply_layout(
+        &mut self,
+        _: &dyn WindowSystem,
+        screen: Rectangle,
+        _: &GeneralConfig,
+        stack: &Option<Stack<Window>>,
+    ) -> Vec<(Window, Rectangle)> {
+        match *stack {
+            Some(ref s) => {
+                let ws = s.integrate();
+                self.update(&ws);
+
+                // Empty columns don't take up any space
+                let used: Vec<(f32, Vec<Window>)> = self
+                    .columns
+                    .iter()
+                    .zip(self.widths.iter())
+                    .filter(|&(c, _)| !c.is_empty())
+                    .map(|(c, &w)| {
+                        let column = ws.iter().filter(|w| c.contains(w)).copied().collect();
+                        (w, column)
+                    })
+                    .collect();
+                let total: f32 = used.iter().map(|&(w, _)| w).sum();
+
+                let Rectangle(sx, sy, sw, sh) = screen;
+                let mut x = sx;
+                let mut result = Vec::new();
+                for (i, (weight, column)) in used.iter().enumerate() {
+                    let cw = if i == used.len() - 1 {
+                        (sx + sw as i32 - x) as u32
+                    } else {
+                        (sw as f32 * weight / total) as u32
+                    };
+                    let n = column.len() as u32;
+                    let h = sh / n;
+                    for (j, &window) in column.iter().enumerate() {
+                        let j = j as u32;
+                        let y = sy + (j * h) as i32;
+                        let wh = if j == n - 1 { sh - j * h } else { h };
+                        result.push((window, Rectangle(x, y, cw, wh)));
+                    }
+                    x += cw as i32;
+                }
+                result
+            }
+            None => Vec::new(),
+        }
+    }
+
+    fn apply_message(
+        &mut self,
+        message: LayoutMessage,
+        _: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        _: &GeneralConfig,
+    ) -> bool {
+        let d = self.increment_ratio;
+        let focused_column = stack.as_ref().and_then(|s| self.column_of(s.focus));
+        match message {
+            LayoutMessage::Increase => {
+                if let Some(c) = focused_column {
+                    self.resize_column(c, d);
+                }
+                true
+            }
+            LayoutMessage::Decrease => {
+                if let Some(c) = focused_column {
+                    self.resize_column(c, -d);
+                }
+                true
+            }
+            LayoutMessage::IncreaseMaster => {
+                self.add_column();
+                true
+            }
+            LayoutMessage::DecreaseMaster => {
+                self.remove_column();
+                true
+            }
+            LayoutMessage::IncreaseSlave => {
+                if let Some(ref s) = *stack {
+                    self.move_window(s.focus, 1);
+                }
+                true
+            }
+            LayoutMessage::DecreaseSlave => {
+                if let Some(ref s) = *stack {
+                    self.move_window(s.focus, -1);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn description(&self) -> String {
+        "Columns".to_owned()
+    }
+
+    fn copy(&self) -> Box<dyn Layout> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,228 @@
+extern crate wtftw;
+
+use self::wtftw::config::GeneralConfig;
+use self::wtftw::core::stack::Stack;
+use self::wtftw::layout::Layout;
+use self::wtftw::layout::LayoutMessage;
+use self::wtftw::window_system::Rectangle;
+use self::wtftw::window_system::Window;
+use self::wtftw::window_system::WindowSystem;
+use super::layout_info::LayoutInfo;
+use super::message_util::same_message;
+use super::stack_util::stack_from_windows;
+use std::collections::BTreeMap;
+
+/// Decides which windows go into the left part of a ComboLayout
+#[derive(Clone)]
+pub enum ComboRule {
+    /// The first n windows of the stack
+    FirstWindows(usize),
+    /// All windows with the given class name
+    ClassName(String),
+}
+
+/// What a message bound in a ComboLayout does
+#[derive(Clone, Copy)]
+pub enum ComboAction {
+    /// Make the left part wider
+    Grow,
+    /// Make the left part narrower
+    Shrink,
+    /// Move the focused window to the other part
+    MoveToOtherSide,
+}
+
+/// A layout that splits the screen into a left and a right part
+/// and runs a separate layout in each of them. Windows are put
+/// into the left part according to the given rule, unless they
+/// have been moved explicitly.
+///
+/// The messages bound to a ComboAction are handled by the
+/// ComboLayout itself, all other messages go to the layout
+/// holding the focused window.
+pub struct ComboLayout {
+    pub ratio: f32,
+    pub increment_ratio: f32,
+    pub shortcuts: Vec<(LayoutMessage, ComboAction)>,
+    rule: ComboRule,
+    moved: BTreeMap<Window, bool>,
+    left: Box<dyn Layout>,
+    right: Box<dyn Layout>,
+}
+
+impl ComboLayout {
+    pub fn boxed_new(
+        ratio: f32,
+        rule: ComboRule,
+        shortcuts: Vec<(LayoutMessage, ComboAction)>,
+        left: Box<dyn Layout>,
+        right: Box<dyn Layout>,
+    ) -> Box<dyn Layout> {
+        Box::new(ComboLayout {
+            ratio,
+            increment_ratio: 0.03,
+            shortcuts,
+            rule,
+            moved: BTreeMap::new(),
+            left: left.copy(),
+            right: right.copy(),
+        })
+    }
+
+    /// Whether the window at the given stack position belongs
+    /// into the left part
+    fn is_left(&self, window_system: &dyn WindowSystem, index: usize, window: Window) -> bool {
+        match self.moved.get(&window) {
+            Some(&left) => left,
+            None => match self.rule {
+                ComboRule::FirstWindows(n) => index < n,
+                ComboRule::ClassName(ref class) => window_system.get_class_name(window) == *class,
+            },
+        }
+    }
+
+    /// Split the stack into the stacks for the left and the right layout
+    fn split_stack(
+        &self,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+    ) -> (Option<Stack<Window>>, Option<Stack<Window>>) {
+        match *stack {
+            Some(ref s) => {
+                let mut left = Vec::new();
+                let mut right = Vec::new();
+                for (i, w) in s.integrate().into_iter().enumerate() {
+                    if self.is_left(window_system, i, w) {
+                        left.push(w);
+                    } else {
+                        right.push(w);
+                    }
+                }
+                (
+                    stack_from_windows(&left, s.focus),
+                    stack_from_windows(&right, s.focus),
+                )
+            }
+            None => (None, None),
+        }
+    }
+
+    /// The structured description of the layout and its children
+    pub fn info(&self) -> LayoutInfo {
+        LayoutInfo::new("Combo")
+            .with_parameter(self.ratio)
+            .with_child(&*self.left)
+            .with_child(&*self.right)
+    }
+}
+
+impl Layout for ComboLayout {
+    fn apply_layout(
+        &mut self,
+        window_system: &dyn WindowSystem,
+        screen: Rectangle,
+        config: &GeneralConfig,
+        stack: &Option<Stack<Window>>,
+    ) -> Vec<(Window, Rectangle)> {
+        if let Some(ref s) = *stack {
+            let windows = s.integrate();
+            self.moved.retain(|w, _| windows.contains(w));
+        }
+
+        let (left_stack, right_stack) = self.split_stack(window_system, stack);
+
+        // If one side is empty, the other one gets the whole screen
+        let Rectangle(x, y, w, h) = screen;
+        let lw = (w as f32 * self.ratio) as u32;
+        let (left_screen, right_screen) = match (&left_stack, &right_stack) {
+            (Some(_), None) | (None, Some(_)) => (screen, screen),
+            _ => (
+                Rectangle(x, y, lw, h),
+                Rectangle(x + lw as i32, y, w - lw, h),
+            ),
+        };
+
+        let left = self
+            .left
+            .apply_layout(window_system, left_screen, config, &left_stack);
+        let right = self
+            .right
+            .apply_layout(window_system, right_screen, config, &right_stack);
+        left.into_iter().chain(right).collect()
+    }
+
+    fn apply_message(
+        &mut self,
+        message: LayoutMessage,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) -> bool {
+        let (left_stack, right_stack) = self.split_stack(window_system, stack);
+        let focus_left = match *stack {
+            Some(ref s) => left_stack.as_ref().map(|l| l.focus) == Some(s.focus),
+            None => true,
+        };
+
+        let action = self
+            .shortcuts
+            .iter()
+            .find(|&&(m, _)| same_message(m, message))
+            .map(|&(_, a)| a);
+
+        match action {
+            Some(ComboAction::Grow) => {
+                if self.ratio + self.increment_ratio < 1.0 {
+                    self.ratio += self.increment_ratio;
+                }
+                true
+            }
+            Some(ComboAction::Shrink) => {
+                if self.ratio - self.increment_ratio > 0.0 {
+                    self.ratio -= self.increment_ratio;
+                }
+                true
+            }
+            Some(ComboAction::MoveToOtherSide) => {
+                if let Some(ref s) = *stack {
+                    self.moved.insert(s.focus, !focus_left);
+                }
+                true
+            }
+            None if focus_left => {
+                self.left
+                    .apply_message(message, window_system, &left_stack, config)
+            }
+            None => self
+                .right
+                .apply_message(message, window_system, &right_stack, config),
+        }
+    }
+
+    fn description(&self) -> String {
+        self.info().to_string()
+    }
+
+    fn copy(&self) -> Box<dyn Layout> {
+        Box::new(ComboLayout {
+            ratio: self.ratio,
+            increment_ratio: self.increment_ratio,
+            shortcuts: self.shortcuts.clone(),
+            rule: self.rule.clone(),
+            moved: self.moved.clone(),
+            left: self.left.copy(),
+            right: self.right.copy(),
+        })
+    }
+
+    fn unhook(
+        &self,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) {
+        let (left_stack, right_stack) = self.split_stack(window_system, stack);
+        self.left.unhook(window_system, &left_stack, config);
+        self.right.unhook(window_system, &right_stack, config);
+    }
+}
@@ -0,0 +1,112 @@
+extern crate wtftw;
+
+use self::wtftw::config::GeneralConfig;
+use self::wtftw::core::stack::Stack;
+use self::wtftw::layout::Layout;
+use self::wtftw::layout::LayoutMessage;
+use self::wtftw::window_system::Rectangle;
+use self::wtftw::window_system::Window;
+use self::wtftw::window_system::WindowSystem;
+use super::rect_util::{split_horizontally, split_vertically};
+use std::borrow::ToOwned;
+
+/// A layout that stacks the master windows at the top
+/// of the screen, each one using the full width, and
+/// puts all remaining windows next to each other in a
+/// row of "dishes" at the bottom.
+///
+/// `Increase` and `Decrease` change the height of the
+/// dishes row, `IncreaseMaster` and `DecreaseMaster`
+/// change the number of master windows.
+#[derive(Clone, Copy)]
+pub struct DishesLayout {
+    pub num_master: u32,
+    pub increment_ratio: f32,
+    pub ratio: f32,
+}
+
+impl DishesLayout {
+    pub fn boxed_new() -> Box<dyn Layout> {
+        Box::new(DishesLayout {
+            num_master: 1,
+            increment_ratio: 0.03,
+            ratio: 0.25,
+        })
+    }
+
+    fn tile(&self, screen: Rectangle, num_windows: u32) -> Vec<Rectangle> {
+        if num_windows <= self.num_master {
+            return split_vertically(num_windows, screen);
+        }
+
+        let Rectangle(sx, sy, sw, sh) = screen;
+        let dish_h = (sh as f32 * self.ratio) as u32;
+        let masters = split_vertically(self.num_master, Rectangle(sx, sy, sw, sh - dish_h));
+        let dishes = split_horizontally(
+            num_windows - self.num_master,
+            Rectangle(sx, sy + (sh - dish_h) as i32, sw, dish_h),
+        );
+        masters.into_iter().chain(dishes).collect()
+    }
+}
+
+impl Layout for DishesLayout {
+    fn apply_layout(
+        &mut self,
+        _: &dyn WindowSystem,
+        screen: Rectangle,
+        _: &GeneralConfig,
+        stack: &Option<Stack<Window>>,
+    ) -> Vec<(Window, Rectangle)> {
+        match *stack {
+            Some(ref s) => {
+                let ws = s.integrate();
+                let rs = self.tile(screen, ws.len() as u32);
+                ws.into_iter().zip(rs).collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    fn apply_message(
+        &mut self,
+        message: LayoutMessage,
+        _: &dyn WindowSystem,
+        _: &Option<Stack<Window>>,
+        _: &GeneralConfig,
+    ) -> bool {
+        match message {
+            LayoutMessage::Increase => {
+                if self.ratio + self.increment_ratio < 1.0 {
+                    self.ratio += self.increment_ratio;
+                }
+                true
+            }
+            LayoutMessage::Decrease => {
+                if self.ratio - self.increment_ratio > 0.0 {
+                    self.ratio -= self.increment_ratio;
+                }
+                true
+            }
+            LayoutMessage::IncreaseMaster => {
+                self.num_master += 1;
+                true
+            }
+            LayoutMessage::DecreaseMaster => {
+                if self.num_master > 1 {
+                    self.num_master -= 1
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn description(&self) -> String {
+        "Dishes".to_owned()
+    }
+
+    fn copy(&self) -> Box<dyn Layout> {
+        Box::new(*self)
+    }
+}
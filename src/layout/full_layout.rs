@@ -16,15 +16,11 @@ impl Layout for FullLayout {
         &mut self,
         _: &dyn WindowSystem,
         screen: Rectangle,
-        config: &GeneralConfig,
+        _: &GeneralConfig,
         stack: &Option<Stack<Window>>,
     ) -> Vec<(Window, Rectangle)> {
         match *stack {
-            Some(ref st) => {
-                let bw = 2 * config.border_width;
-                let Rectangle(x, y, sw, sh) = screen;
-                vec![(st.focus, Rectangle(x, y, sw + bw, sh + bw))]
-            }
+            Some(ref st) => vec![(st.focus, screen)],
             None => Vec::new(),
         }
     }
@@ -2,24 +2,235 @@ extern crate wtftw;
 
 use self::wtftw::config::GeneralConfig;
 use self::wtftw::core::stack::Stack;
+use self::wtftw::layout::Direction;
 use self::wtftw::layout::Layout;
 use self::wtftw::layout::LayoutMessage;
 use self::wtftw::window_system::Rectangle;
 use self::wtftw::window_system::Window;
 use self::wtftw::window_system::WindowSystem;
+use super::layout_info::LayoutInfo;
+use super::message_util::same_message;
+use super::rect_util::{bounds, covers};
 
+/// The gaps of a GapLayout in pixels. The outer gaps separate the
+/// windows from the screen edges, the inner gap separates adjacent
+/// windows from each other.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Gaps {
+    pub top: u32,
+    pub bottom: u32,
+    pub left: u32,
+    pub right: u32,
+    pub inner: u32,
+}
+
+impl Gaps {
+    /// The same outer gap on all sides and the same space between
+    /// adjacent windows
+    pub fn uniform(gap: u32) -> Gaps {
+        Gaps {
+            top: gap,
+            bottom: gap,
+            left: gap,
+            right: gap,
+            inner: gap,
+        }
+    }
+
+    fn outer_mut(&mut self, direction: Direction) -> &mut u32 {
+        match direction {
+            Direction::Up => &mut self.top,
+            Direction::Down => &mut self.bottom,
+            Direction::Left => &mut self.left,
+            Direction::Right => &mut self.right,
+        }
+    }
+
+    /// Shrink a window's rectangle by the gaps that apply to its sides.
+    /// Sides at the edge of the area the windows are tiled in get the
+    /// outer gaps, all others half of the inner gap. If the window
+    /// would become smaller than the minimum size, the gaps of this
+    /// window are reduced, but the window never grows beyond the
+    /// rectangle it was given.
+    pub fn apply(&self, limits: GapLimits, area: Rectangle, rect: Rectangle) -> Rectangle {
+        let Rectangle(sx, sy, sw, sh) = area;
+        let Rectangle(x, y, w, h) = rect;
+        let inner_before = self.inner / 2;
+        let inner_after = self.inner - inner_before;
+
+        let left = if x <= sx { self.left } else { inner_before };
+        let top = if y <= sy { self.top } else { inner_before };
+        let right = if x + w as i32 >= sx + sw as i32 {
+            self.right
+        } else {
+            inner_after
+        };
+        let bottom = if y + h as i32 >= sy + sh as i32 {
+            self.bottom
+        } else {
+            inner_after
+        };
+
+        let (x, w) = clamp_gaps(x, w, left, right, limits.min_width);
+        let (y, h) = clamp_gaps(y, h, top, bottom, limits.min_height);
+        Rectangle(x, y, w, h)
+    }
+}
+
+/// Limits that keep a GapLayout usable when the gaps get large
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GapLimits {
+    /// The width a window is never shrunk below by its gaps
+    pub min_width: u32,
+    /// The height a window is never shrunk below by its gaps
+    pub min_height: u32,
+    /// The largest value any gap can be increased to
+    pub max_gap: u32,
+}
+
+impl Default for GapLimits {
+    fn default() -> GapLimits {
+        GapLimits {
+            min_width: 10,
+            min_height: 10,
+            max_gap: 100,
+        }
+    }
+}
+
+/// The gaps a message of a GapLayout adjusts
+#[derive(Clone, Copy)]
+pub enum GapTarget {
+    /// All outer gaps and the inner gap
+    All,
+    /// The gap between adjacent windows
+    Inner,
+    /// All outer gaps
+    Outer,
+    /// The outer gap at the given screen edge
+    Side(Direction),
+}
+
+/// A layout modifier that adds gaps around the windows of the
+/// contained layout.
+///
+/// Whether a side of a window gets the outer or the inner gap
+/// depends on whether it touches the edge of the screen. Adjacent
+/// windows get half of the inner gap each, so the space between
+/// them is exactly the inner gap. `IncreaseGap` and `DecreaseGap`
+/// adjust all gaps by one pixel, further messages can be bound to
+/// individual gaps with `with_shortcuts`.
+///
+/// With smart gaps, windows get no gaps while they are the only
+/// window of the contained layout or cover the whole screen.
 pub struct GapLayout {
-    gap: u32,
+    pub gaps: Gaps,
+    pub smart: bool,
+    pub limits: GapLimits,
+    pub shortcuts: Vec<(LayoutMessage, GapTarget, i32)>,
     layout: Box<dyn Layout>,
 }
 
 impl GapLayout {
     pub fn boxed_new(gap: u32, layout: Box<dyn Layout>) -> Box<dyn Layout> {
-        Box::new(GapLayout {
-            gap,
+        GapLayout::new(Gaps::uniform(gap), layout).boxed()
+    }
+
+    /// Create a new GapLayout to be configured further before
+    /// boxing it, e.g. for smart gaps
+    pub fn new(gaps: Gaps, layout: Box<dyn Layout>) -> GapLayout {
+        GapLayout {
+            gaps,
+            smart: false,
+            limits: GapLimits::default(),
+            shortcuts: Vec::new(),
             layout: layout.copy(),
-        })
+        }
+    }
+
+    pub fn with_smart(mut self, smart: bool) -> GapLayout {
+        self.smart = smart;
+        self
+    }
+
+    /// Use the given limits instead of the defaults
+    pub fn with_limits(mut self, limits: GapLimits) -> GapLayout {
+        self.limits = limits;
+        self
+    }
+
+    /// Change the targeted gaps by the given number of pixels
+    /// when receiving the associated message
+    pub fn with_shortcuts(mut self, shortcuts: Vec<(LayoutMessage, GapTarget, i32)>) -> GapLayout {
+        self.shortcuts = shortcuts;
+        self
+    }
+
+    pub fn boxed(self) -> Box<dyn Layout> {
+        Box::new(self)
+    }
+
+    /// Change the targeted gaps by the given number of pixels,
+    /// keeping them between zero and the maximum gap
+    pub fn adjust(&mut self, target: GapTarget, delta: i32) {
+        let max = self.limits.max_gap as i64;
+        let change = |gap: &mut u32, delta: i32| {
+            *gap = (*gap as i64 + delta as i64).max(0).min(max) as u32;
+        };
+
+        match target {
+            GapTarget::All => {
+                self.adjust(GapTarget::Outer, delta);
+                self.adjust(GapTarget::Inner, delta);
+            }
+            GapTarget::Inner => change(&mut self.gaps.inner, delta),
+            GapTarget::Outer => {
+                change(&mut self.gaps.top, delta);
+                change(&mut self.gaps.bottom, delta);
+                change(&mut self.gaps.left, delta);
+                change(&mut self.gaps.right, delta);
+            }
+            GapTarget::Side(d) => change(self.gaps.outer_mut(d), delta),
+        }
     }
+
+    fn apply_gaps(&self, area: Rectangle, rect: Rectangle) -> Rectangle {
+        self.gaps.apply(self.limits, area, rect)
+    }
+
+    /// The structured description of the layout and its children
+    pub fn info(&self) -> LayoutInfo {
+        let g = self.gaps;
+        let info = LayoutInfo::new("Gaps");
+        let info = if g == Gaps::uniform(g.inner) {
+            info.with_parameter(g.inner)
+        } else {
+            info.with_parameter(g.top)
+                .with_parameter(g.bottom)
+                .with_parameter(g.left)
+                .with_parameter(g.right)
+                .with_parameter(g.inner)
+        };
+        let info = if self.smart {
+            info.with_parameter("smart")
+        } else {
+            info
+        };
+        info.with_child(&*self.layout)
+    }
+}
+
+/// Apply the gaps before and after a span of the given position and
+/// size, reducing them proportionally to keep at least `min` pixels
+fn clamp_gaps(pos: i32, size: u32, before: u32, after: u32, min: u32) -> (i32, u32) {
+    let gaps = before as u64 + after as u64;
+    let new_size = (size as u64)
+        .saturating_sub(gaps)
+        .max(min as u64)
+        .min(size as u64);
+    let available = size as u64 - new_size;
+    let before = (before as u64 * available).checked_div(gaps).unwrap_or(0);
+    (pos + before as i32, new_size as u32)
 }
 
 impl Layout for GapLayout {
@@ -34,14 +245,19 @@ impl Layout for GapLayout {
             .layout
             .apply_layout(window_system, screen, config, stack);
 
-        let g = self.gap;
+        // The contained layout might not use the whole screen, e.g.
+        // around an AvoidStrutsLayout, so the outer gaps go around
+        // the area its windows actually cover
+        let area = bounds(layout.iter().map(|&(_, r)| r)).unwrap_or(screen);
+        let alone = layout.len() == 1;
         layout
             .iter()
-            .map(|&(win, Rectangle(x, y, w, h))| {
-                (
-                    win,
-                    Rectangle(x + g as i32, y + g as i32, w - 2 * g, h - 2 * g),
-                )
+            .map(|&(win, rect)| {
+                if self.smart && (alone || covers(rect, area)) {
+                    (win, rect)
+                } else {
+                    (win, self.apply_gaps(area, rect))
+                }
             })
             .collect()
     }
@@ -53,15 +269,23 @@ impl Layout for GapLayout {
         stack: &Option<Stack<Window>>,
         config: &GeneralConfig,
     ) -> bool {
+        let shortcut = self
+            .shortcuts
+            .iter()
+            .find(|&&(m, _, _)| same_message(m, message))
+            .map(|&(_, target, delta)| (target, delta));
+        if let Some((target, delta)) = shortcut {
+            self.adjust(target, delta);
+            return true;
+        }
+
         match message {
             LayoutMessage::IncreaseGap => {
-                self.gap += 1;
+                self.adjust(GapTarget::All, 1);
                 true
             }
             LayoutMessage::DecreaseGap => {
-                if self.gap > 0 {
-                    self.gap -= 1;
-                }
+                self.adjust(GapTarget::All, -1);
                 true
             }
             _ => self
@@ -71,13 +295,109 @@ impl Layout for GapLayout {
     }
 
     fn description(&self) -> String {
-        self.layout.description()
+        self.info().to_string()
     }
 
     fn copy(&self) -> Box<dyn Layout> {
         Box::new(GapLayout {
-            gap: self.gap,
+            gaps: self.gaps,
+            smart: self.smart,
+            limits: self.limits,
+            shortcuts: self.shortcuts.clone(),
             layout: self.layout.copy(),
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::wtftw::window_system::Rectangle;
+    use super::{GapLayout, GapLimits, GapTarget, Gaps};
+    use crate::layout::binary_space_partition::BinarySpacePartition;
+    use crate::layout::full_layout::FullLayout;
+    use crate::layout::resizable_tall_layout::ResizableTallLayout;
+    use std::iter;
+
+    const SCREEN: Rectangle = Rectangle(0, 0, 200, 100);
+
+    fn gap_layout(gap: u32) -> GapLayout {
+        GapLayout {
+            gaps: Gaps::uniform(gap),
+            smart: false,
+            limits: GapLimits::default(),
+            shortcuts: Vec::new(),
+            layout: Box::new(FullLayout),
+        }
+    }
+
+    fn tall_tiles(n: u32) -> Vec<Rectangle> {
+        ResizableTallLayout::tile(0.5, iter::repeat_n(1.0, n as usize), SCREEN, 1, n)
+    }
+
+    fn bsp_tiles(n: usize) -> Vec<Rectangle> {
+        (1..n)
+            .fold(BinarySpacePartition::empty().split_nth(0), |bsp, i| {
+                bsp.split_nth(i - 1)
+            })
+            .rectangles(SCREEN)
+    }
+
+    fn assert_within_and_min_size(layout: &GapLayout, tiles: &[Rectangle]) {
+        let limits = layout.limits;
+        for &tile in tiles {
+            let Rectangle(x, y, w, h) = tile;
+            let Rectangle(gx, gy, gw, gh) = layout.apply_gaps(SCREEN, tile);
+            assert!(gx >= x && gx + gw as i32 <= x + w as i32, "{:?}", tile);
+            assert!(gy >= y && gy + gh as i32 <= y + h as i32, "{:?}", tile);
+            assert!(gw >= limits.min_width.min(w), "{:?}", tile);
+            assert!(gh >= limits.min_height.min(h), "{:?}", tile);
+        }
+    }
+
+    #[test]
+    fn inner_gap_separates_adjacent_windows() {
+        let layout = gap_layout(6);
+        let tiles = tall_tiles(2);
+        let Rectangle(lx, _, lw, _) = layout.apply_gaps(SCREEN, tiles[0]);
+        let Rectangle(rx, _, rw, _) = layout.apply_gaps(SCREEN, tiles[1]);
+        assert_eq!(lx, 6);
+        assert_eq!(rx - (lx + lw as i32), 6);
+        assert_eq!(rx + rw as i32, 200 - 6);
+    }
+
+    #[test]
+    fn tiny_tall_tiles_keep_minimum_size() {
+        let tiles = tall_tiles(30);
+        for &gap in [0, 3, 30, 100].iter() {
+            assert_within_and_min_size(&gap_layout(gap), &tiles);
+        }
+    }
+
+    #[test]
+    fn tiny_bsp_tiles_keep_minimum_size() {
+        let tiles = bsp_tiles(16);
+        assert_eq!(tiles.len(), 16);
+        for &gap in [0, 3, 30, 100].iter() {
+            assert_within_and_min_size(&gap_layout(gap), &tiles);
+        }
+    }
+
+    #[test]
+    fn tiles_smaller_than_minimum_size_are_left_alone() {
+        let layout = gap_layout(50);
+        let tile = Rectangle(40, 40, 5, 3);
+        assert_eq!(layout.apply_gaps(SCREEN, tile), tile);
+    }
+
+    #[test]
+    fn gaps_stop_at_maximum() {
+        let mut layout = gap_layout(0);
+        for _ in 0..1000 {
+            layout.adjust(GapTarget::All, 1);
+        }
+        assert_eq!(layout.gaps, Gaps::uniform(layout.limits.max_gap));
+
+        layout.adjust(GapTarget::Inner, -1000);
+        assert_eq!(layout.gaps.inner, 0);
+    }
+}
@@ -7,19 +7,82 @@ use self::wtftw::layout::LayoutMessage;
 use self::wtftw::window_system::Rectangle;
 use self::wtftw::window_system::Window;
 use self::wtftw::window_system::WindowSystem;
+use super::layout_info::LayoutInfo;
+use super::message_util::same_message;
+
+/// Identifies a layout inside a LayoutCollection
+#[derive(Clone)]
+pub enum LayoutSelector {
+    /// The layout at the given position
+    Index(usize),
+    /// The first layout with the given description, or with
+    /// the given name anywhere from the layout itself down to
+    /// its innermost active layout, e.g. `Gaps` or `BSP`
+    Description(String),
+}
 
 pub struct LayoutCollection {
     pub layouts: Vec<Box<dyn Layout>>,
     pub current: usize,
+    pub shortcuts: Vec<(LayoutMessage, LayoutSelector)>,
 }
 
 impl LayoutCollection {
     pub fn boxed_new(layouts: Vec<Box<dyn Layout>>) -> Box<dyn Layout> {
+        LayoutCollection::boxed_with_shortcuts(layouts, Vec::new())
+    }
+
+    /// Create a new LayoutCollection that jumps directly to the
+    /// selected layout when receiving the associated message.
+    pub fn boxed_with_shortcuts(
+        layouts: Vec<Box<dyn Layout>>,
+        shortcuts: Vec<(LayoutMessage, LayoutSelector)>,
+    ) -> Box<dyn Layout> {
         Box::new(LayoutCollection {
             layouts,
             current: 0,
+            shortcuts,
         })
     }
+
+    /// Find the index of the selected layout
+    pub fn find(&self, selector: &LayoutSelector) -> Option<usize> {
+        match *selector {
+            LayoutSelector::Index(i) if i < self.layouts.len() => Some(i),
+            LayoutSelector::Index(_) => None,
+            LayoutSelector::Description(ref d) => self.layouts.iter().position(|l| {
+                l.description() == *d || LayoutInfo::of(&**l).path().iter().any(|i| i.name == *d)
+            }),
+        }
+    }
+
+    /// Switch to the layout at the given index, unhooking the
+    /// current one. Returns false if there is no such layout.
+    pub fn select(
+        &mut self,
+        index: usize,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) -> bool {
+        if index >= self.layouts.len() {
+            return false;
+        }
+
+        if index != self.current {
+            self.layouts[self.current].unhook(window_system, stack, config);
+            self.current = index;
+        }
+        true
+    }
+
+    /// The structured description of the layout and its children
+    pub fn info(&self) -> LayoutInfo {
+        self.layouts.iter().fold(
+            LayoutInfo::new("Collection").with_current(self.current),
+            |info, l| info.with_child(&**l),
+        )
+    }
 }
 
 impl Layout for LayoutCollection {
@@ -40,29 +103,37 @@ impl Layout for LayoutCollection {
         stack: &Option<Stack<Window>>,
         config: &GeneralConfig,
     ) -> bool {
+        let shortcut = self
+            .shortcuts
+            .iter()
+            .find(|(m, _)| same_message(*m, message))
+            .and_then(|(_, s)| self.find(s));
+        if let Some(index) = shortcut {
+            return self.select(index, window_system, stack, config);
+        }
+
         match message {
             LayoutMessage::Next => {
-                self.layouts[self.current].unhook(window_system, stack, config);
-                self.current = (self.current + 1) % self.layouts.len();
-                true
+                let next = (self.current + 1) % self.layouts.len();
+                self.select(next, window_system, stack, config)
             }
             LayoutMessage::Prev => {
-                self.layouts[self.current].unhook(window_system, stack, config);
-                self.current = (self.current + (self.layouts.len() - 1)) % self.layouts.len();
-                true
+                let prev = (self.current + (self.layouts.len() - 1)) % self.layouts.len();
+                self.select(prev, window_system, stack, config)
             }
             _ => self.layouts[self.current].apply_message(message, window_system, stack, config),
         }
     }
 
     fn description(&self) -> String {
-        self.layouts[self.current].description()
+        self.info().to_string()
     }
 
     fn copy(&self) -> Box<dyn Layout> {
         Box::new(LayoutCollection {
             current: self.current,
             layouts: self.layouts.iter().map(|x| x.copy()).collect(),
+            shortcuts: self.shortcuts.clone(),
         })
     }
 }
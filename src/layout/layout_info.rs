@@ -0,0 +1,241 @@
+extern crate wtftw;
+
+use self::wtftw::layout::Layout;
+use std::fmt;
+
+/// A structured description of a layout and the layouts it contains.
+///
+/// Layouts in this crate that contain other layouts have an `info()`
+/// method returning the whole tree. Their `description()` renders it
+/// as e.g. `Gaps(4) > Struts(Up) > BSP` or `Combo(0.5) > {Full | BSP}`.
+/// Containers that show one of their layouts at a time only render
+/// the current one, e.g. `Collection[1] > BSP`.
+///
+/// Names and parameters are escaped with a backslash where they
+/// contain one of `\()[]|>,{}`, so the rendered tree can be recovered
+/// from any boxed layout with `LayoutInfo::of`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct LayoutInfo {
+    pub name: String,
+    pub parameters: Vec<String>,
+    pub current: Option<usize>,
+    pub children: Vec<LayoutInfo>,
+}
+
+impl LayoutInfo {
+    pub fn new(name: &str) -> LayoutInfo {
+        LayoutInfo {
+            name: name.to_owned(),
+            parameters: Vec::new(),
+            current: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Recover the structured description of the given layout.
+    /// Boxed layouts only expose their description, so it is
+    /// parsed. Descriptions that weren't rendered by a LayoutInfo,
+    /// e.g. of layouts from other crates, end up as a node with
+    /// the whole description as its name.
+    pub fn of(layout: &dyn Layout) -> LayoutInfo {
+        let description = layout.description();
+        let info = LayoutInfo::parse(&description);
+        if info.to_string() == description {
+            info
+        } else {
+            LayoutInfo::new(&description)
+        }
+    }
+
+    pub fn with_parameter<T: fmt::Display>(mut self, parameter: T) -> LayoutInfo {
+        self.parameters.push(parameter.to_string());
+        self
+    }
+
+    pub fn with_current(mut self, current: usize) -> LayoutInfo {
+        self.current = Some(current);
+        self
+    }
+
+    pub fn with_child(mut self, layout: &dyn Layout) -> LayoutInfo {
+        self.children.push(LayoutInfo::of(layout));
+        self
+    }
+
+    /// The layouts from this one down to the innermost one,
+    /// following the current child or the only child down the tree
+    pub fn path(&self) -> Vec<&LayoutInfo> {
+        let child = match self.current {
+            Some(i) if i < self.children.len() => self.children.get(i),
+            _ if self.children.len() == 1 => self.children.first(),
+            _ => None,
+        };
+        let mut path = vec![self];
+        if let Some(c) = child {
+            path.extend(c.path());
+        }
+        path
+    }
+
+    /// The innermost layout, following the current child
+    /// or the only child down the tree
+    pub fn leaf(&self) -> &LayoutInfo {
+        self.path().last().copied().unwrap_or(self)
+    }
+
+    /// Parse a description rendered by a LayoutInfo
+    pub fn parse(description: &str) -> LayoutInfo {
+        let mut parser = Parser {
+            input: description,
+            pos: 0,
+        };
+        parser.node()
+    }
+}
+
+impl fmt::Display for LayoutInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", escape(&self.name))?;
+        if !self.parameters.is_empty() {
+            let parameters: Vec<String> = self.parameters.iter().map(|p| escape(p)).collect();
+            write!(f, "({})", parameters.join(", "))?;
+        }
+        if let Some(current) = self.current {
+            write!(f, "[{}]", current)?;
+            if let Some(child) = self.children.get(current) {
+                return write!(f, " > {}", child);
+            }
+        }
+        match self.children.len() {
+            0 => Ok(()),
+            1 => write!(f, " > {}", self.children[0]),
+            _ => {
+                let children: Vec<String> = self.children.iter().map(|c| c.to_string()).collect();
+                write!(f, " > {{{}}}", children.join(" | "))
+            }
+        }
+    }
+}
+
+/// Characters that have a meaning in a rendered LayoutInfo
+const SPECIAL: &str = "\\()[]|>,{}";
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if SPECIAL.contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.extend(chars.next()),
+            _ => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+/// The byte offsets of the characters in the text that aren't
+/// escaped with a backslash
+fn unescaped_positions(text: &str) -> Vec<usize> {
+    let mut positions = Vec::new();
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else {
+            positions.push(i);
+        }
+    }
+    positions
+}
+
+/// Split the text at every unescaped occurrence of the separator
+fn split_unescaped<'a>(text: &'a str, separator: &str) -> Vec<&'a str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    for i in unescaped_positions(text) {
+        if i >= start && text[i..].starts_with(separator) {
+            parts.push(&text[start..i]);
+            start = i + separator.len();
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Read everything up to the first of the given delimiters
+    /// that isn't escaped
+    fn until(&mut self, delimiters: &[&str]) -> &'a str {
+        let rest = self.rest();
+        let end = unescaped_positions(rest)
+            .into_iter()
+            .find(|&i| delimiters.iter().any(|d| rest[i..].starts_with(d)))
+            .unwrap_or(rest.len());
+        self.pos += end;
+        &rest[..end]
+    }
+
+    fn node(&mut self) -> LayoutInfo {
+        let name = self.until(&["(", "[", " > ", " | ", "}"]).trim();
+        let mut info = LayoutInfo::new(&unescape(name));
+
+        if self.eat("(") {
+            info.parameters = split_unescaped(self.until(&[")"]), ", ")
+                .into_iter()
+                .map(unescape)
+                .collect();
+            self.eat(")");
+        }
+
+        if self.eat("[") {
+            info.current = self.until(&["]"]).parse().ok();
+            self.eat("]");
+        }
+
+        if self.eat(" > ") {
+            if self.eat("{") {
+                loop {
+                    info.children.push(self.node());
+                    if !self.eat(" | ") {
+                        break;
+                    }
+                }
+                self.eat("}");
+            } else {
+                info.children.push(self.node());
+            }
+        }
+
+        info
+    }
+}
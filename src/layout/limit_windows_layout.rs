@@ -0,0 +1,214 @@
+extern crate wtftw;
+
+use self::wtftw::config::GeneralConfig;
+use self::wtftw::core::stack::Stack;
+use self::wtftw::layout::Layout;
+use self::wtftw::layout::LayoutMessage;
+use self::wtftw::window_system::Rectangle;
+use self::wtftw::window_system::Window;
+use self::wtftw::window_system::WindowSystem;
+use super::layout_info::LayoutInfo;
+use super::message_util::same_message;
+use super::stack_util::filter_stack;
+
+/// Decides which windows a LimitWindowsLayout shows
+#[derive(Clone, Copy, PartialEq)]
+pub enum LimitMode {
+    /// The first windows of the stack
+    First,
+    /// The windows around the focused one
+    AroundFocus,
+    /// The windows selected by the user plus the focused one
+    Selection,
+}
+
+/// What a message bound in a LimitWindowsLayout does
+#[derive(Clone, Copy)]
+pub enum LimitAction {
+    /// Show one more window
+    Increase,
+    /// Show one window less
+    Decrease,
+    /// Add the focused window to the selection or remove it again
+    ToggleSelection,
+}
+
+/// A layout modifier that only passes a limited number of
+/// windows on to its contained layout and hides the rest.
+///
+/// In selection mode, the focused window and the selection are
+/// shown. If the selection grows past the limit, the window
+/// selected first is dropped from it. While an unselected window
+/// is focused, the window selected first is hidden to make room
+/// for it. All messages without a LimitAction are passed on to
+/// the contained layout.
+pub struct LimitWindowsLayout {
+    pub limit: usize,
+    pub mode: LimitMode,
+    pub shortcuts: Vec<(LayoutMessage, LimitAction)>,
+    selection: Vec<Window>,
+    layout: Box<dyn Layout>,
+}
+
+impl LimitWindowsLayout {
+    /// Show only the first `limit` windows
+    pub fn boxed_new(limit: usize, layout: Box<dyn Layout>) -> Box<dyn Layout> {
+        LimitWindowsLayout::boxed_with_mode(limit, LimitMode::First, Vec::new(), layout)
+    }
+
+    pub fn boxed_with_mode(
+        limit: usize,
+        mode: LimitMode,
+        shortcuts: Vec<(LayoutMessage, LimitAction)>,
+        layout: Box<dyn Layout>,
+    ) -> Box<dyn Layout> {
+        Box::new(LimitWindowsLayout {
+            limit: limit.max(1),
+            mode,
+            shortcuts,
+            selection: Vec::new(),
+            layout: layout.copy(),
+        })
+    }
+
+    /// The part of the stack that is visible
+    fn limit_stack(&self, stack: &Option<Stack<Window>>) -> Option<Stack<Window>> {
+        stack.as_ref().and_then(|s| {
+            let windows = s.integrate();
+            let visible: Vec<Window> = match self.mode {
+                LimitMode::First => windows.into_iter().take(self.limit).collect(),
+                LimitMode::AroundFocus => {
+                    let n = windows.len();
+                    let start =
+                        s.up.len()
+                            .saturating_sub(self.limit / 2)
+                            .min(n.saturating_sub(self.limit));
+                    windows.into_iter().skip(start).take(self.limit).collect()
+                }
+                LimitMode::Selection => {
+                    // Without the focused window in it, only the
+                    // windows selected last fit next to it
+                    let room = if self.selection.contains(&s.focus) {
+                        self.limit
+                    } else {
+                        self.limit - 1
+                    };
+                    let selected = &self.selection[self.selection.len().saturating_sub(room)..];
+                    windows
+                        .into_iter()
+                        .filter(|&w| w == s.focus || selected.contains(&w))
+                        .collect()
+                }
+            };
+            filter_stack(s, |w| visible.contains(&w))
+        })
+    }
+
+    fn toggle_selection(&mut self, window: Window) {
+        if self.selection.contains(&window) {
+            self.selection.retain(|&w| w != window);
+        } else {
+            self.selection.push(window);
+            self.truncate_selection();
+        }
+    }
+
+    /// Drop the windows selected first until the selection
+    /// fits into the limit
+    fn truncate_selection(&mut self) {
+        if self.selection.len() > self.limit {
+            let excess = self.selection.len() - self.limit;
+            self.selection.drain(..excess);
+        }
+    }
+
+    /// The structured description of the layout and its children
+    pub fn info(&self) -> LayoutInfo {
+        LayoutInfo::new("Limit")
+            .with_parameter(self.limit)
+            .with_child(&*self.layout)
+    }
+}
+
+impl Layout for LimitWindowsLayout {
+    fn apply_layout(
+        &mut self,
+        window_system: &dyn WindowSystem,
+        screen: Rectangle,
+        config: &GeneralConfig,
+        stack: &Option<Stack<Window>>,
+    ) -> Vec<(Window, Rectangle)> {
+        if let Some(ref s) = *stack {
+            let windows = s.integrate();
+            self.selection.retain(|w| windows.contains(w));
+        }
+
+        let limited = self.limit_stack(stack);
+        self.layout
+            .apply_layout(window_system, screen, config, &limited)
+    }
+
+    fn apply_message(
+        &mut self,
+        message: LayoutMessage,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) -> bool {
+        let action = self
+            .shortcuts
+            .iter()
+            .find(|&&(m, _)| same_message(m, message))
+            .map(|&(_, a)| a);
+        let focus = stack.as_ref().map(|s| s.focus);
+
+        match action {
+            Some(LimitAction::Increase) => {
+                self.limit += 1;
+                true
+            }
+            Some(LimitAction::Decrease) => {
+                if self.limit > 1 {
+                    self.limit -= 1;
+                    self.truncate_selection();
+                }
+                true
+            }
+            Some(LimitAction::ToggleSelection) if self.mode == LimitMode::Selection => {
+                if let Some(w) = focus {
+                    self.toggle_selection(w);
+                }
+                true
+            }
+            _ => {
+                let limited = self.limit_stack(stack);
+                self.layout
+                    .apply_message(message, window_system, &limited, config)
+            }
+        }
+    }
+
+    fn description(&self) -> String {
+        self.info().to_string()
+    }
+
+    fn copy(&self) -> Box<dyn Layout> {
+        Box::new(LimitWindowsLayout {
+            limit: self.limit,
+            mode: self.mode,
+            shortcuts: self.shortcuts.clone(),
+            selection: self.selection.clone(),
+            layout: self.layout.copy(),
+        })
+    }
+
+    fn unhook(
+        &self,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) {
+        let limited = self.limit_stack(stack);
+        self.layout.unhook(window_system, &limited, config);
+    }
+}
@@ -0,0 +1,203 @@
+extern crate wtftw;
+
+use self::wtftw::config::GeneralConfig;
+use self::wtftw::core::stack::Stack;
+use self::wtftw::layout::Layout;
+use self::wtftw::layout::LayoutMessage;
+use self::wtftw::window_system::Rectangle;
+use self::wtftw::window_system::Window;
+use self::wtftw::window_system::WindowSystem;
+use super::layout_info::LayoutInfo;
+use super::message_util::same_message;
+
+/// A layout modifier that enlarges the focused window of
+/// its contained layout by a given factor around the
+/// window's centre, clamped to the screen.
+///
+/// The magnify and shrink messages change the magnification,
+/// the toggle message turns the magnifier on or off. All other
+/// messages are passed on to the contained layout.
+pub struct MagnifierLayout {
+    pub magnification: f32,
+    pub increment: f32,
+    pub enabled: bool,
+    pub skip_master: bool,
+    pub num_master: u32,
+    pub magnify: LayoutMessage,
+    pub shrink: LayoutMessage,
+    pub toggle: LayoutMessage,
+    layout: Box<dyn Layout>,
+}
+
+impl MagnifierLayout {
+    /// Create a new MagnifierLayout that magnifies the focused
+    /// window of the given layout by `magnification`
+    pub fn boxed_new(
+        magnification: f32,
+        magnify: LayoutMessage,
+        shrink: LayoutMessage,
+        toggle: LayoutMessage,
+        layout: Box<dyn Layout>,
+    ) -> Box<dyn Layout> {
+        Box::new(MagnifierLayout {
+            magnification,
+            increment: 0.1,
+            enabled: true,
+            skip_master: false,
+            num_master: 1,
+            magnify,
+            shrink,
+            toggle,
+            layout: layout.copy(),
+        })
+    }
+
+    /// Create a new MagnifierLayout that leaves the focused
+    /// window alone if it is one of the master windows. The
+    /// contained layout has to start with `num_master` master
+    /// windows, changes are tracked from there.
+    pub fn boxed_new_skip_master(
+        magnification: f32,
+        num_master: u32,
+        magnify: LayoutMessage,
+        shrink: LayoutMessage,
+        toggle: LayoutMessage,
+        layout: Box<dyn Layout>,
+    ) -> Box<dyn Layout> {
+        Box::new(MagnifierLayout {
+            magnification,
+            increment: 0.1,
+            enabled: true,
+            skip_master: true,
+            num_master,
+            magnify,
+            shrink,
+            toggle,
+            layout: layout.copy(),
+        })
+    }
+
+    fn magnified(&self, Rectangle(x, y, w, h): Rectangle, screen: Rectangle) -> Rectangle {
+        let Rectangle(sx, sy, sw, sh) = screen;
+        let nw = ((w as f32 * self.magnification) as u32).min(sw);
+        let nh = ((h as f32 * self.magnification) as u32).min(sh);
+        let nx = (x + (w as i32 - nw as i32) / 2)
+            .max(sx)
+            .min(sx + (sw - nw) as i32);
+        let ny = (y + (h as i32 - nh as i32) / 2)
+            .max(sy)
+            .min(sy + (sh - nh) as i32);
+        Rectangle(nx, ny, nw, nh)
+    }
+
+    /// The structured description of the layout and its children
+    pub fn info(&self) -> LayoutInfo {
+        let info = LayoutInfo::new("Magnifier").with_parameter(self.magnification);
+        let info = if self.enabled {
+            info
+        } else {
+            info.with_parameter("off")
+        };
+        info.with_child(&*self.layout)
+    }
+}
+
+impl Layout for MagnifierLayout {
+    fn apply_layout(
+        &mut self,
+        window_system: &dyn WindowSystem,
+        screen: Rectangle,
+        config: &GeneralConfig,
+        stack: &Option<Stack<Window>>,
+    ) -> Vec<(Window, Rectangle)> {
+        let layout = self
+            .layout
+            .apply_layout(window_system, screen, config, stack);
+
+        match *stack {
+            Some(ref s) => {
+                let is_master = (s.up.len() as u32) < self.num_master;
+                if !self.enabled || (self.skip_master && is_master) {
+                    return layout;
+                }
+
+                // Put the magnified window first, so it ends up on top
+                let focused = layout
+                    .iter()
+                    .filter(|&&(w, _)| w == s.focus)
+                    .map(|&(w, r)| (w, self.magnified(r, screen)));
+                let rest = layout.iter().filter(|&&(w, _)| w != s.focus).copied();
+                focused.chain(rest).collect()
+            }
+            None => layout,
+        }
+    }
+
+    fn apply_message(
+        &mut self,
+        message: LayoutMessage,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) -> bool {
+        if same_message(message, self.magnify) {
+            self.magnification += self.increment;
+            return true;
+        }
+        if same_message(message, self.shrink) {
+            if self.magnification - self.increment >= 1.0 {
+                self.magnification -= self.increment;
+            }
+            return true;
+        }
+        if same_message(message, self.toggle) {
+            self.enabled = !self.enabled;
+            return true;
+        }
+
+        match message {
+            LayoutMessage::IncreaseMaster => {
+                self.num_master += 1;
+                self.layout
+                    .apply_message(message, window_system, stack, config)
+            }
+            LayoutMessage::DecreaseMaster => {
+                if self.num_master > 1 {
+                    self.num_master -= 1;
+                }
+                self.layout
+                    .apply_message(message, window_system, stack, config)
+            }
+            _ => self
+                .layout
+                .apply_message(message, window_system, stack, config),
+        }
+    }
+
+    fn description(&self) -> String {
+        self.info().to_string()
+    }
+
+    fn copy(&self) -> Box<dyn Layout> {
+        Box::new(MagnifierLayout {
+            magnification: self.magnification,
+            increment: self.increment,
+            enabled: self.enabled,
+            skip_master: self.skip_master,
+            num_master: self.num_master,
+            magnify: self.magnify,
+            shrink: self.shrink,
+            toggle: self.toggle,
+            layout: self.layout.copy(),
+        })
+    }
+
+    fn unhook(
+        &self,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) {
+        self.layout.unhook(window_system, stack, config);
+    }
+}
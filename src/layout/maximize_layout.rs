@@ -0,0 +1,122 @@
+extern crate wtftw;
+
+use self::wtftw::config::GeneralConfig;
+use self::wtftw::core::stack::Stack;
+use self::wtftw::layout::Layout;
+use self::wtftw::layout::LayoutMessage;
+use self::wtftw::window_system::Rectangle;
+use self::wtftw::window_system::Window;
+use self::wtftw::window_system::WindowSystem;
+use super::full_layout::FullLayout;
+use super::layout_info::LayoutInfo;
+use super::message_util::same_message;
+
+/// A layout modifier that lets a single window cover the whole
+/// screen on top of the contained layout, while all other windows
+/// keep their place underneath it.
+///
+/// The toggle message maximizes the focused window, or restores
+/// it if it is already maximized.
+pub struct MaximizeLayout {
+    maximized: Option<Window>,
+    toggle: LayoutMessage,
+    layout: Box<dyn Layout>,
+}
+
+impl MaximizeLayout {
+    pub fn boxed_new(toggle: LayoutMessage, layout: Box<dyn Layout>) -> Box<dyn Layout> {
+        Box::new(MaximizeLayout {
+            maximized: None,
+            toggle,
+            layout: layout.copy(),
+        })
+    }
+
+    /// The window that is currently maximized
+    pub fn maximized(&self) -> Option<Window> {
+        self.maximized
+    }
+
+    /// The structured description of the layout and its children
+    pub fn info(&self) -> LayoutInfo {
+        LayoutInfo::new("Maximize").with_child(&*self.layout)
+    }
+}
+
+impl Layout for MaximizeLayout {
+    fn apply_layout(
+        &mut self,
+        window_system: &dyn WindowSystem,
+        screen: Rectangle,
+        config: &GeneralConfig,
+        stack: &Option<Stack<Window>>,
+    ) -> Vec<(Window, Rectangle)> {
+        // Forget windows that have been closed in the meantime
+        if let Some(window) = self.maximized {
+            if !stack.iter().any(|s| s.integrate().contains(&window)) {
+                self.maximized = None;
+            }
+        }
+
+        let layout = self
+            .layout
+            .apply_layout(window_system, screen, config, stack);
+
+        match self.maximized {
+            Some(window) => {
+                let full = Some(Stack::from_element(window));
+                // The maximized window goes first, so it's drawn on top
+                FullLayout
+                    .apply_layout(window_system, screen, config, &full)
+                    .into_iter()
+                    .chain(layout.into_iter().filter(|&(w, _)| w != window))
+                    .collect()
+            }
+            None => layout,
+        }
+    }
+
+    fn apply_message(
+        &mut self,
+        message: LayoutMessage,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) -> bool {
+        if !same_message(message, self.toggle) {
+            return self
+                .layout
+                .apply_message(message, window_system, stack, config);
+        }
+
+        if let Some(ref s) = *stack {
+            self.maximized = if self.maximized == Some(s.focus) {
+                None
+            } else {
+                Some(s.focus)
+            };
+        }
+        true
+    }
+
+    fn description(&self) -> String {
+        self.info().to_string()
+    }
+
+    fn copy(&self) -> Box<dyn Layout> {
+        Box::new(MaximizeLayout {
+            maximized: self.maximized,
+            toggle: self.toggle,
+            layout: self.layout.copy(),
+        })
+    }
+
+    fn unhook(
+        &self,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) {
+        self.layout.unhook(window_system, stack, config);
+    }
+}
@@ -0,0 +1,18 @@
+extern crate wtftw;
+
+use self::wtftw::layout::LayoutMessage;
+use std::mem;
+
+/// Whether a message matches a message a layout was configured with.
+///
+/// Layouts that take their messages as parameters all compare them
+/// this way: messages match if they are the same variant, and for
+/// `TreeExpandTowards` and `TreeShrinkFrom` also have the same
+/// direction, so each direction can be bound to something else.
+pub fn same_message(a: LayoutMessage, b: LayoutMessage) -> bool {
+    match (a, b) {
+        (LayoutMessage::TreeExpandTowards(x), LayoutMessage::TreeExpandTowards(y))
+        | (LayoutMessage::TreeShrinkFrom(x), LayoutMessage::TreeShrinkFrom(y)) => x == y,
+        _ => mem::discriminant(&a) == mem::discriminant(&b),
+    }
+}
@@ -0,0 +1,134 @@
+extern crate wtftw;
+
+use self::wtftw::config::GeneralConfig;
+use self::wtftw::core::stack::Stack;
+use self::wtftw::layout::Layout;
+use self::wtftw::layout::LayoutMessage;
+use self::wtftw::window_system::Rectangle;
+use self::wtftw::window_system::Window;
+use self::wtftw::window_system::WindowSystem;
+use super::layout_info::LayoutInfo;
+use super::message_util::same_message;
+use super::stack_util::filter_stack;
+
+/// A layout modifier that hides minimized windows from the
+/// contained layout until they are restored.
+///
+/// The minimize message minimizes the focused window and the
+/// restore message brings back the window minimized last. The
+/// windows are only left out of the stack handed to the contained
+/// layout, so restored windows return to their original position
+/// in the stack.
+pub struct MinimizeLayout {
+    minimized: Vec<Window>,
+    minimize: LayoutMessage,
+    restore: LayoutMessage,
+    layout: Box<dyn Layout>,
+}
+
+impl MinimizeLayout {
+    pub fn boxed_new(
+        minimize: LayoutMessage,
+        restore: LayoutMessage,
+        layout: Box<dyn Layout>,
+    ) -> Box<dyn Layout> {
+        Box::new(MinimizeLayout {
+            minimized: Vec::new(),
+            minimize,
+            restore,
+            layout: layout.copy(),
+        })
+    }
+
+    /// The minimized windows, in the order they were minimized
+    pub fn minimized(&self) -> &[Window] {
+        &self.minimized
+    }
+
+    fn visible_stack(&self, stack: &Option<Stack<Window>>) -> Option<Stack<Window>> {
+        stack
+            .as_ref()
+            .and_then(|s| filter_stack(s, |w| !self.minimized.contains(&w)))
+    }
+
+    /// The structured description of the layout and its children
+    pub fn info(&self) -> LayoutInfo {
+        let info = LayoutInfo::new("Minimize");
+        let info = if self.minimized.is_empty() {
+            info
+        } else {
+            info.with_parameter(self.minimized.len())
+        };
+        info.with_child(&*self.layout)
+    }
+}
+
+impl Layout for MinimizeLayout {
+    fn apply_layout(
+        &mut self,
+        window_system: &dyn WindowSystem,
+        screen: Rectangle,
+        config: &GeneralConfig,
+        stack: &Option<Stack<Window>>,
+    ) -> Vec<(Window, Rectangle)> {
+        // Forget windows that have been closed in the meantime
+        match *stack {
+            Some(ref s) => {
+                let windows = s.integrate();
+                self.minimized.retain(|w| windows.contains(w));
+            }
+            None => self.minimized.clear(),
+        }
+
+        let visible = self.visible_stack(stack);
+        self.layout
+            .apply_layout(window_system, screen, config, &visible)
+    }
+
+    fn apply_message(
+        &mut self,
+        message: LayoutMessage,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) -> bool {
+        if same_message(message, self.minimize) {
+            if let Some(ref s) = *stack {
+                if !self.minimized.contains(&s.focus) {
+                    self.minimized.push(s.focus);
+                }
+            }
+            true
+        } else if same_message(message, self.restore) {
+            self.minimized.pop();
+            true
+        } else {
+            let visible = self.visible_stack(stack);
+            self.layout
+                .apply_message(message, window_system, &visible, config)
+        }
+    }
+
+    fn description(&self) -> String {
+        self.info().to_string()
+    }
+
+    fn copy(&self) -> Box<dyn Layout> {
+        Box::new(MinimizeLayout {
+            minimized: self.minimized.clone(),
+            minimize: self.minimize,
+            restore: self.restore,
+            layout: self.layout.copy(),
+        })
+    }
+
+    fn unhook(
+        &self,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) {
+        let visible = self.visible_stack(stack);
+        self.layout.unhook(window_system, &visible, config);
+    }
+}
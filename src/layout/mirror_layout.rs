@@ -8,6 +8,7 @@ use self::wtftw::layout::LayoutMessage;
 use self::wtftw::window_system::Rectangle;
 use self::wtftw::window_system::Window;
 use self::wtftw::window_system::WindowSystem;
+use super::layout_info::LayoutInfo;
 
 /// A simple layout container that just
 /// rotates the layout of its contained layout
@@ -21,6 +22,11 @@ impl MirrorLayout {
     pub fn boxed_new(layout: Box<dyn Layout>) -> Box<dyn Layout> {
         Box::new(MirrorLayout { layout })
     }
+
+    /// The structured description of the layout and its children
+    pub fn info(&self) -> LayoutInfo {
+        LayoutInfo::new("Mirror").with_child(&*self.layout)
+    }
 }
 
 impl Layout for MirrorLayout {
@@ -52,7 +58,7 @@ impl Layout for MirrorLayout {
     }
 
     fn description(&self) -> String {
-        self.layout.description()
+        self.info().to_string()
     }
 
     fn copy(&self) -> Box<dyn Layout> {
@@ -1,21 +1,62 @@
 pub mod avoid_struts_layout;
 pub mod binary_space_partition;
+pub mod border_policy;
+pub mod cascade_layout;
 pub mod center_layout;
+pub mod columns_layout;
+pub mod combo_layout;
+pub mod dishes_layout;
 pub mod full_layout;
 pub mod gap_layout;
 pub mod layout_collection;
+pub mod layout_info;
+pub mod limit_windows_layout;
+pub mod magnifier_layout;
+pub mod maximize_layout;
+pub mod minimize_layout;
 pub mod mirror_layout;
+pub mod mosaic_layout;
+pub mod multi_toggle_layout;
+pub mod on_screen_layout;
+pub mod on_workspace_layout;
+pub mod one_big_layout;
 pub mod resizable_tall_layout;
+pub mod strut_cache;
+pub mod sub_layout;
+pub mod toggle_layout;
 pub mod with_borders_layout;
+pub mod work_area;
+mod message_util;
+mod rect_util;
+mod stack_util;
 pub mod no_borders_layout;
 
 pub use self::avoid_struts_layout::*;
 pub use self::binary_space_partition::*;
+pub use self::border_policy::*;
+pub use self::cascade_layout::*;
 pub use self::center_layout::*;
+pub use self::columns_layout::*;
+pub use self::combo_layout::*;
+pub use self::dishes_layout::*;
 pub use self::full_layout::*;
 pub use self::gap_layout::*;
 pub use self::layout_collection::*;
+pub use self::layout_info::*;
+pub use self::limit_windows_layout::*;
+pub use self::magnifier_layout::*;
+pub use self::maximize_layout::*;
+pub use self::minimize_layout::*;
 pub use self::mirror_layout::*;
+pub use self::mosaic_layout::*;
+pub use self::multi_toggle_layout::*;
+pub use self::on_screen_layout::*;
+pub use self::on_workspace_layout::*;
+pub use self::one_big_layout::*;
 pub use self::resizable_tall_layout::*;
+pub use self::strut_cache::*;
+pub use self::sub_layout::*;
+pub use self::toggle_layout::*;
 pub use self::with_borders_layout::*;
+pub use self::work_area::*;
 pub use self::no_borders_layout::*;
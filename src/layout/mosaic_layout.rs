@@ -0,0 +1,182 @@
+extern crate wtftw;
+
+use self::wtftw::config::GeneralConfig;
+use self::wtftw::core::stack::Stack;
+use self::wtftw::layout::Layout;
+use self::wtftw::layout::LayoutMessage;
+use self::wtftw::window_system::Rectangle;
+use self::wtftw::window_system::Window;
+use self::wtftw::window_system::WindowSystem;
+use std::borrow::ToOwned;
+use std::collections::BTreeMap;
+
+/// The range the weights are kept in, so repeatedly growing or
+/// shrinking a window can't squeeze the others out of the screen
+const MIN_WEIGHT: f32 = 0.05;
+const MAX_WEIGHT: f32 = 20.0;
+
+/// A layout that gives every window a relative area weight
+/// and partitions the screen into rows (or columns) of tiles,
+/// so that each tile's area is proportional to the weight of
+/// its window while keeping the tiles as square as possible.
+///
+/// The weights are stored per window, so they follow a window
+/// around when the stack is reordered. `Increase` and `Decrease`
+/// grow or shrink the weight of the focused window.
+#[derive(Clone)]
+pub struct MosaicLayout {
+    pub increment_ratio: f32,
+    weights: BTreeMap<Window, f32>,
+}
+
+impl MosaicLayout {
+    pub fn boxed_new() -> Box<dyn Layout> {
+        Box::new(MosaicLayout {
+            increment_ratio: 1.25,
+            weights: BTreeMap::new(),
+        })
+    }
+
+    /// The area weight of the given window
+    pub fn weight(&self, window: Window) -> f32 {
+        self.weights.get(&window).copied().unwrap_or(1.0)
+    }
+
+    fn scale_weight(&mut self, window: Window, f: f32) {
+        let w = (self.weight(window) * f).clamp(MIN_WEIGHT, MAX_WEIGHT);
+        self.weights.insert(window, w);
+    }
+
+    /// Split the weights into `k` consecutive groups of
+    /// roughly the same total weight
+    fn group(weights: &[f32], k: usize) -> Vec<Vec<f32>> {
+        let total: f32 = weights.iter().sum();
+        let target = total / k as f32;
+        let mut groups = vec![Vec::new(); k];
+        let mut sum = 0.0;
+        for &w in weights {
+            let index = (((sum + w / 2.0) / target) as usize).min(k - 1);
+            groups[index].push(w);
+            sum += w;
+        }
+        groups.into_iter().filter(|g| !g.is_empty()).collect()
+    }
+
+    /// Lay out the groups as rows, each row getting a height
+    /// proportional to its total weight and each tile in a row a
+    /// width proportional to its own weight
+    fn rows(groups: &[Vec<f32>], Rectangle(sx, sy, sw, sh): Rectangle) -> Vec<Rectangle> {
+        let total: f32 = groups.iter().flat_map(|g| g.iter()).sum();
+        let mut result = Vec::new();
+        let mut y = sy;
+        for (i, group) in groups.iter().enumerate() {
+            let group_total: f32 = group.iter().sum();
+            let h = if i == groups.len() - 1 {
+                (sy + sh as i32 - y) as u32
+            } else {
+                (sh as f32 * group_total / total) as u32
+            };
+            let mut x = sx;
+            for (j, &w) in group.iter().enumerate() {
+                let tw = if j == group.len() - 1 {
+                    (sx + sw as i32 - x) as u32
+                } else {
+                    (sw as f32 * w / group_total) as u32
+                };
+                result.push(Rectangle(x, y, tw, h));
+                x += tw as i32;
+            }
+            y += h as i32;
+        }
+        result
+    }
+
+    fn transpose(&Rectangle(x, y, w, h): &Rectangle) -> Rectangle {
+        Rectangle(y, x, h, w)
+    }
+
+    /// How far the tiles are from being square, weighted by area
+    fn badness(rects: &[Rectangle]) -> f32 {
+        rects
+            .iter()
+            .map(|&Rectangle(_, _, w, h)| {
+                let (w, h) = (w.max(1) as f32, h.max(1) as f32);
+                (w / h).max(h / w) * w * h
+            })
+            .sum()
+    }
+
+    fn tile(weights: &[f32], screen: Rectangle) -> Vec<Rectangle> {
+        (1..=weights.len())
+            .flat_map(|k| {
+                let groups = MosaicLayout::group(weights, k);
+                let rows = MosaicLayout::rows(&groups, screen);
+                let columns = MosaicLayout::rows(&groups, MosaicLayout::transpose(&screen))
+                    .iter()
+                    .map(MosaicLayout::transpose)
+                    .collect();
+                vec![rows, columns]
+            })
+            .min_by(|a, b| {
+                MosaicLayout::badness(a)
+                    .partial_cmp(&MosaicLayout::badness(b))
+                    .unwrap()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Layout for MosaicLayout {
+    fn apply_layout(
+        &mut self,
+        _: &dyn WindowSystem,
+        screen: Rectangle,
+        _: &GeneralConfig,
+        stack: &Option<Stack<Window>>,
+    ) -> Vec<(Window, Rectangle)> {
+        match *stack {
+            Some(ref s) => {
+                let ws = s.integrate();
+                self.weights.retain(|w, _| ws.contains(w));
+                let weights: Vec<f32> = ws.iter().map(|&w| self.weight(w)).collect();
+                ws.into_iter()
+                    .zip(MosaicLayout::tile(&weights, screen))
+                    .collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    fn apply_message(
+        &mut self,
+        message: LayoutMessage,
+        _: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        _: &GeneralConfig,
+    ) -> bool {
+        let d = self.increment_ratio;
+        match message {
+            LayoutMessage::Increase => {
+                if let Some(ref s) = *stack {
+                    self.scale_weight(s.focus, d);
+                }
+                true
+            }
+            LayoutMessage::Decrease => {
+                if let Some(ref s) = *stack {
+                    self.scale_weight(s.focus, 1.0 / d);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn description(&self) -> String {
+        "Mosaic".to_owned()
+    }
+
+    fn copy(&self) -> Box<dyn Layout> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,194 @@
+extern crate wtftw;
+
+use self::wtftw::config::GeneralConfig;
+use self::wtftw::core::stack::Stack;
+use self::wtftw::layout::mirror_rect;
+use self::wtftw::layout::Layout;
+use self::wtftw::layout::LayoutMessage;
+use self::wtftw::window_system::Rectangle;
+use self::wtftw::window_system::Window;
+use self::wtftw::window_system::WindowSystem;
+use super::full_layout::FullLayout;
+use super::gap_layout::{GapLimits, Gaps};
+use super::layout_info::LayoutInfo;
+use super::message_util::same_message;
+use super::rect_util::{bounds, with_border};
+use std::collections::BTreeSet;
+
+/// The transformers a MultiToggleLayout can switch on and off.
+/// Active transformers are applied in the order they are
+/// declared here, i.e. mirroring is applied first and removing
+/// the borders last.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Transformer {
+    Mirror,
+    Full,
+    Gaps,
+    NoBorders,
+}
+
+/// A layout modifier that wraps a base layout with several
+/// transformers that can be switched on and off independently.
+///
+/// Each transformer is toggled by the message it is registered
+/// with. Messages that don't toggle a transformer are passed
+/// on to the base layout.
+pub struct MultiToggleLayout {
+    pub gaps: Gaps,
+    active: BTreeSet<Transformer>,
+    toggles: Vec<(LayoutMessage, Transformer)>,
+    layout: Box<dyn Layout>,
+}
+
+impl MultiToggleLayout {
+    /// Create a new MultiToggleLayout around the given layout,
+    /// where each transformer is toggled by the given message.
+    pub fn boxed_new(
+        toggles: Vec<(LayoutMessage, Transformer)>,
+        gaps: Gaps,
+        layout: Box<dyn Layout>,
+    ) -> Box<dyn Layout> {
+        Box::new(MultiToggleLayout {
+            gaps,
+            active: BTreeSet::new(),
+            toggles,
+            layout: layout.copy(),
+        })
+    }
+
+    pub fn is_active(&self, transformer: Transformer) -> bool {
+        self.active.contains(&transformer)
+    }
+
+    fn toggle(&mut self, transformer: Transformer) {
+        if !self.active.remove(&transformer) {
+            self.active.insert(transformer);
+        }
+    }
+
+    fn restore_borders(
+        &self,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) {
+        if let Some(ref s) = *stack {
+            for window in s.integrate().into_iter() {
+                window_system.set_window_border_width(window, config.border_width);
+            }
+        }
+    }
+
+    /// The structured description of the layout and its children
+    pub fn info(&self) -> LayoutInfo {
+        self.active
+            .iter()
+            .fold(LayoutInfo::new("MultiToggle"), |info, t| {
+                info.with_parameter(format!("{:?}", t))
+            })
+            .with_child(&*self.layout)
+    }
+}
+
+impl Layout for MultiToggleLayout {
+    fn apply_layout(
+        &mut self,
+        window_system: &dyn WindowSystem,
+        screen: Rectangle,
+        config: &GeneralConfig,
+        stack: &Option<Stack<Window>>,
+    ) -> Vec<(Window, Rectangle)> {
+        let mirror = self.is_active(Transformer::Mirror);
+
+        let layout = if self.is_active(Transformer::Full) {
+            FullLayout.apply_layout(window_system, screen, config, stack)
+        } else if mirror {
+            self.layout
+                .apply_layout(window_system, mirror_rect(&screen), config, stack)
+                .iter()
+                .map(|&(w, r)| (w, mirror_rect(&r)))
+                .collect()
+        } else {
+            self.layout
+                .apply_layout(window_system, screen, config, stack)
+        };
+
+        let layout = if self.is_active(Transformer::Gaps) {
+            let area = bounds(layout.iter().map(|&(_, r)| r)).unwrap_or(screen);
+            layout
+                .into_iter()
+                .map(|(w, r)| (w, self.gaps.apply(GapLimits::default(), area, r)))
+                .collect()
+        } else {
+            layout
+        };
+
+        if self.is_active(Transformer::NoBorders) {
+            if let Some(ref s) = *stack {
+                for window in s.integrate().into_iter() {
+                    window_system.set_window_border_width(window, 0);
+                }
+            }
+            layout
+                .into_iter()
+                .map(|(w, r)| (w, with_border(r, 0, config.border_width)))
+                .collect()
+        } else {
+            layout
+        }
+    }
+
+    fn apply_message(
+        &mut self,
+        message: LayoutMessage,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) -> bool {
+        let toggled: Vec<Transformer> = self
+            .toggles
+            .iter()
+            .filter(|&&(m, _)| same_message(m, message))
+            .map(|&(_, t)| t)
+            .collect();
+
+        if toggled.is_empty() {
+            return self
+                .layout
+                .apply_message(message, window_system, stack, config);
+        }
+
+        for transformer in toggled {
+            self.toggle(transformer);
+            if transformer == Transformer::NoBorders && !self.is_active(transformer) {
+                self.restore_borders(window_system, stack, config);
+            }
+        }
+        true
+    }
+
+    fn description(&self) -> String {
+        self.info().to_string()
+    }
+
+    fn copy(&self) -> Box<dyn Layout> {
+        Box::new(MultiToggleLayout {
+            gaps: self.gaps,
+            active: self.active.clone(),
+            toggles: self.toggles.clone(),
+            layout: self.layout.copy(),
+        })
+    }
+
+    fn unhook(
+        &self,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) {
+        if self.is_active(Transformer::NoBorders) {
+            self.restore_borders(window_system, stack, config);
+        }
+        self.layout.unhook(window_system, stack, config);
+    }
+}
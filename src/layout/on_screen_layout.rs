@@ -0,0 +1,148 @@
+extern crate wtftw;
+
+use self::wtftw::config::GeneralConfig;
+use self::wtftw::core::stack::Stack;
+use self::wtftw::layout::Layout;
+use self::wtftw::layout::LayoutMessage;
+use self::wtftw::window_system::Rectangle;
+use self::wtftw::window_system::Window;
+use self::wtftw::window_system::WindowSystem;
+use super::layout_info::LayoutInfo;
+
+/// A condition on the screen a layout is applied on
+#[derive(Clone, Copy)]
+pub enum ScreenCondition {
+    /// The screen's width divided by its height is above the value
+    WiderThan(f32),
+    /// The screen's width divided by its height is below the value
+    NarrowerThan(f32),
+    /// The screen is at least the given number of pixels wide
+    MinWidth(u32),
+    /// The screen is at most the given number of pixels wide
+    MaxWidth(u32),
+}
+
+impl ScreenCondition {
+    pub fn matches(&self, Rectangle(_, _, w, h): Rectangle) -> bool {
+        let aspect = w as f32 / h.max(1) as f32;
+        match *self {
+            ScreenCondition::WiderThan(r) => aspect > r,
+            ScreenCondition::NarrowerThan(r) => aspect < r,
+            ScreenCondition::MinWidth(m) => w >= m,
+            ScreenCondition::MaxWidth(m) => w <= m,
+        }
+    }
+}
+
+/// A layout container that picks the first layout whose condition
+/// matches the screen it is applied on, e.g. a ResizableTallLayout
+/// on landscape and a mirrored one on portrait monitors, falling
+/// back to the default layout if no condition matches.
+///
+/// Every layout keeps its own state, so rotating a monitor back
+/// and forth doesn't lose any configuration. Messages go to the
+/// layout that was used last.
+pub struct OnScreenLayout {
+    current: usize,
+    layouts: Vec<(ScreenCondition, Box<dyn Layout>)>,
+    default: Box<dyn Layout>,
+}
+
+impl OnScreenLayout {
+    pub fn boxed_new(
+        layouts: Vec<(ScreenCondition, Box<dyn Layout>)>,
+        default: Box<dyn Layout>,
+    ) -> Box<dyn Layout> {
+        Box::new(OnScreenLayout {
+            current: layouts.len(),
+            layouts,
+            default,
+        })
+    }
+
+    fn get(&self, index: usize) -> &dyn Layout {
+        match self.layouts.get(index) {
+            Some((_, l)) => &**l,
+            None => &*self.default,
+        }
+    }
+
+    fn get_mut(&mut self, index: usize) -> &mut Box<dyn Layout> {
+        match self.layouts.get_mut(index) {
+            Some((_, l)) => l,
+            None => &mut self.default,
+        }
+    }
+
+    /// The structured description of the layout and its children
+    pub fn info(&self) -> LayoutInfo {
+        self.layouts
+            .iter()
+            .fold(
+                LayoutInfo::new("OnScreen").with_current(self.current),
+                |info, (_, l)| info.with_child(&**l),
+            )
+            .with_child(&*self.default)
+    }
+}
+
+impl Layout for OnScreenLayout {
+    fn apply_layout(
+        &mut self,
+        window_system: &dyn WindowSystem,
+        screen: Rectangle,
+        config: &GeneralConfig,
+        stack: &Option<Stack<Window>>,
+    ) -> Vec<(Window, Rectangle)> {
+        let index = self
+            .layouts
+            .iter()
+            .position(|(c, _)| c.matches(screen))
+            .unwrap_or(self.layouts.len());
+
+        if index != self.current {
+            self.get(self.current).unhook(window_system, stack, config);
+            self.current = index;
+        }
+
+        self.get_mut(index)
+            .apply_layout(window_system, screen, config, stack)
+    }
+
+    fn apply_message(
+        &mut self,
+        message: LayoutMessage,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) -> bool {
+        let current = self.current;
+        self.get_mut(current)
+            .apply_message(message, window_system, stack, config)
+    }
+
+    fn description(&self) -> String {
+        self.info().to_string()
+    }
+
+    fn copy(&self) -> Box<dyn Layout> {
+        Box::new(OnScreenLayout {
+            current: self.current,
+            layouts: self
+                .layouts
+                .iter()
+                .map(|&(c, ref l)| (c, l.copy()))
+                .collect(),
+            default: self.default.copy(),
+        })
+    }
+
+    fn unhook(
+        &self,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) {
+        self.get(self.current).unhook(window_system, stack, config);
+    }
+}
@@ -0,0 +1,172 @@
+extern crate wtftw;
+
+use self::wtftw::config::GeneralConfig;
+use self::wtftw::core::stack::Stack;
+use self::wtftw::layout::Layout;
+use self::wtftw::layout::LayoutMessage;
+use self::wtftw::window_system::Rectangle;
+use self::wtftw::window_system::Window;
+use self::wtftw::window_system::WindowSystem;
+use super::layout_info::LayoutInfo;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+/// Works out the tag of the workspace a layout is applied on
+/// from the windows on it, e.g. from their `_NET_WM_DESKTOP`
+/// property or their class names
+pub type TagResolver = Rc<dyn Fn(&dyn WindowSystem, &Stack<Window>) -> String>;
+
+/// A layout container that picks a different layout depending
+/// on the workspace it is used on, falling back to a default
+/// layout for all workspaces without an explicit one.
+///
+/// Layouts aren't told which workspace they are applied on, so
+/// the tag is asked from the resolver whenever there are windows
+/// to lay out. Every tag gets its own copy of its layout, so the
+/// state of one workspace doesn't leak into another.
+pub struct OnWorkspaceLayout {
+    resolver: TagResolver,
+    tag: Option<String>,
+    layouts: Vec<(String, Box<dyn Layout>)>,
+    default: Box<dyn Layout>,
+    states: BTreeMap<String, Box<dyn Layout>>,
+}
+
+impl OnWorkspaceLayout {
+    pub fn boxed_new(
+        resolver: TagResolver,
+        layouts: Vec<(String, Box<dyn Layout>)>,
+        default: Box<dyn Layout>,
+    ) -> Box<dyn Layout> {
+        Box::new(OnWorkspaceLayout {
+            resolver,
+            tag: None,
+            layouts,
+            default,
+            states: BTreeMap::new(),
+        })
+    }
+
+    /// The tag of the workspace this layout was last applied on
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+
+    fn current(&self) -> &dyn Layout {
+        match self.tag {
+            Some(ref tag) => match self.states.get(tag) {
+                Some(l) => &**l,
+                None => template(&self.layouts, &*self.default, tag),
+            },
+            None => &*self.default,
+        }
+    }
+
+    /// Resolve the tag of the workspace and return the layout of
+    /// that workspace, unhooking the previous one if it changed
+    fn current_mut(
+        &mut self,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) -> &mut Box<dyn Layout> {
+        if let Some(ref s) = *stack {
+            let tag = (self.resolver)(window_system, s);
+            if self.tag.as_ref() != Some(&tag) {
+                self.current().unhook(window_system, stack, config);
+                self.tag = Some(tag);
+            }
+        }
+
+        match self.tag {
+            Some(ref tag) => {
+                let (layouts, default) = (&self.layouts, &self.default);
+                self.states
+                    .entry(tag.clone())
+                    .or_insert_with(|| template(layouts, &**default, tag).copy())
+            }
+            None => &mut self.default,
+        }
+    }
+
+    /// The structured description of the layout and its children
+    pub fn info(&self) -> LayoutInfo {
+        let info = LayoutInfo::new("OnWorkspace");
+        let info = match self.tag {
+            Some(ref tag) => info.with_parameter(tag),
+            None => info,
+        };
+        info.with_child(self.current())
+    }
+}
+
+/// The layout configured for the given tag
+fn template<'a>(
+    layouts: &'a [(String, Box<dyn Layout>)],
+    default: &'a dyn Layout,
+    tag: &str,
+) -> &'a dyn Layout {
+    layouts
+        .iter()
+        .find(|&(t, _)| t == tag)
+        .map_or(default, |(_, l)| &**l)
+}
+
+impl Layout for OnWorkspaceLayout {
+    fn apply_layout(
+        &mut self,
+        window_system: &dyn WindowSystem,
+        screen: Rectangle,
+        config: &GeneralConfig,
+        stack: &Option<Stack<Window>>,
+    ) -> Vec<(Window, Rectangle)> {
+        self.current_mut(window_system, stack, config).apply_layout(
+            window_system,
+            screen,
+            config,
+            stack,
+        )
+    }
+
+    fn apply_message(
+        &mut self,
+        message: LayoutMessage,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) -> bool {
+        self.current_mut(window_system, stack, config)
+            .apply_message(message, window_system, stack, config)
+    }
+
+    fn description(&self) -> String {
+        self.info().to_string()
+    }
+
+    fn copy(&self) -> Box<dyn Layout> {
+        Box::new(OnWorkspaceLayout {
+            resolver: self.resolver.clone(),
+            tag: self.tag.clone(),
+            layouts: self
+                .layouts
+                .iter()
+                .map(|(t, l)| (t.clone(), l.copy()))
+                .collect(),
+            default: self.default.copy(),
+            states: self
+                .states
+                .iter()
+                .map(|(t, l)| (t.clone(), l.copy()))
+                .collect(),
+        })
+    }
+
+    fn unhook(
+        &self,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) {
+        self.current().unhook(window_system, stack, config);
+    }
+}
@@ -0,0 +1,122 @@
+extern crate wtftw;
+
+use self::wtftw::config::GeneralConfig;
+use self::wtftw::core::stack::Stack;
+use self::wtftw::layout::Layout;
+use self::wtftw::layout::LayoutMessage;
+use self::wtftw::window_system::Rectangle;
+use self::wtftw::window_system::Window;
+use self::wtftw::window_system::WindowSystem;
+use super::rect_util::{split_horizontally, split_vertically};
+use std::borrow::ToOwned;
+
+/// A layout that puts one big master window into the
+/// top left corner of the screen, taking up `width_ratio`
+/// of the width and `height_ratio` of the height. The
+/// remaining windows are spread along the bottom and the
+/// right edge, proportional to the length of each edge.
+///
+/// `Increase` and `Decrease` grow or shrink the master
+/// window in both directions.
+#[derive(Clone, Copy)]
+pub struct OneBigLayout {
+    pub increment_ratio: f32,
+    pub width_ratio: f32,
+    pub height_ratio: f32,
+}
+
+impl OneBigLayout {
+    pub fn boxed_new() -> Box<dyn Layout> {
+        Box::new(OneBigLayout {
+            increment_ratio: 0.03,
+            width_ratio: 0.75,
+            height_ratio: 0.75,
+        })
+    }
+
+    fn tile(&self, screen: Rectangle, num_windows: u32) -> Vec<Rectangle> {
+        if num_windows < 2 {
+            return vec![screen];
+        }
+
+        let Rectangle(sx, sy, sw, sh) = screen;
+        let mw = (sw as f32 * self.width_ratio) as u32;
+        let mh = (sh as f32 * self.height_ratio) as u32;
+
+        // Distribute the remaining windows between the bottom
+        // and the right edge according to their lengths
+        let rest = num_windows - 1;
+        let num_bottom = ((rest * mw) as f32 / (mw + sh) as f32).round() as u32;
+        let num_right = rest - num_bottom;
+
+        let master = Rectangle(
+            sx,
+            sy,
+            if num_right == 0 { sw } else { mw },
+            if num_bottom == 0 { sh } else { mh },
+        );
+        let bottom =
+            split_horizontally(num_bottom, Rectangle(sx, sy + mh as i32, master.2, sh - mh));
+        let right = split_vertically(num_right, Rectangle(sx + mw as i32, sy, sw - mw, sh));
+
+        (vec![master])
+            .into_iter()
+            .chain(bottom)
+            .chain(right)
+            .collect()
+    }
+}
+
+impl Layout for OneBigLayout {
+    fn apply_layout(
+        &mut self,
+        _: &dyn WindowSystem,
+        screen: Rectangle,
+        _: &GeneralConfig,
+        stack: &Option<Stack<Window>>,
+    ) -> Vec<(Window, Rectangle)> {
+        match *stack {
+            Some(ref s) => {
+                let ws = s.integrate();
+                let rs = self.tile(screen, ws.len() as u32);
+                ws.into_iter().zip(rs).collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    fn apply_message(
+        &mut self,
+        message: LayoutMessage,
+        _: &dyn WindowSystem,
+        _: &Option<Stack<Window>>,
+        _: &GeneralConfig,
+    ) -> bool {
+        let d = self.increment_ratio;
+        match message {
+            LayoutMessage::Increase => {
+                if self.width_ratio + d < 1.0 && self.height_ratio + d < 1.0 {
+                    self.width_ratio += d;
+                    self.height_ratio += d;
+                }
+                true
+            }
+            LayoutMessage::Decrease => {
+                if self.width_ratio - d > 0.0 && self.height_ratio - d > 0.0 {
+                    self.width_ratio -= d;
+                    self.height_ratio -= d;
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn description(&self) -> String {
+        "OneBig".to_owned()
+    }
+
+    fn copy(&self) -> Box<dyn Layout> {
+        Box::new(*self)
+    }
+}
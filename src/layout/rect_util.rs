@@ -0,0 +1,76 @@
+extern crate wtftw;
+
+use self::wtftw::window_system::Rectangle;
+
+/// Whether the rectangle covers all of the screen
+pub fn covers(Rectangle(x, y, w, h): Rectangle, Rectangle(sx, sy, sw, sh): Rectangle) -> bool {
+    x <= sx
+        && y <= sy
+        && x as i64 + w as i64 >= sx as i64 + sw as i64
+        && y as i64 + h as i64 >= sy as i64 + sh as i64
+}
+
+/// Adjust a window's rectangle for a border width that differs
+/// from the configured one. Rectangles returned by layouts include
+/// the border, and the window manager subtracts the configured
+/// border width on both sides before resizing a window, so the
+/// difference has to be added back to tile exactly.
+pub fn with_border(Rectangle(x, y, w, h): Rectangle, border: u32, config_border: u32) -> Rectangle {
+    let difference = 2 * (config_border as i64 - border as i64);
+    Rectangle(
+        x,
+        y,
+        (w as i64 + difference).max(0) as u32,
+        (h as i64 + difference).max(0) as u32,
+    )
+}
+
+/// Split the rectangle into `num` rows of the same height. The
+/// last row gets the pixels left over by the division.
+pub fn split_vertically(num: u32, Rectangle(sx, sy, sw, sh): Rectangle) -> Vec<Rectangle> {
+    let h = sh / num.max(1);
+    (0..num)
+        .map(|i| {
+            let y = sy + (i * h) as i32;
+            if i == num - 1 {
+                Rectangle(sx, y, sw, sh - i * h)
+            } else {
+                Rectangle(sx, y, sw, h)
+            }
+        })
+        .collect()
+}
+
+/// Split the rectangle into `num` columns of the same width. The
+/// last column gets the pixels left over by the division.
+pub fn split_horizontally(num: u32, Rectangle(sx, sy, sw, sh): Rectangle) -> Vec<Rectangle> {
+    let w = sw / num.max(1);
+    (0..num)
+        .map(|i| {
+            let x = sx + (i * w) as i32;
+            if i == num - 1 {
+                Rectangle(x, sy, sw - i * w, sh)
+            } else {
+                Rectangle(x, sy, w, sh)
+            }
+        })
+        .collect()
+}
+
+/// The smallest rectangle containing all of the rectangles
+pub fn bounds<I>(rects: I) -> Option<Rectangle>
+where
+    I: IntoIterator<Item = Rectangle>,
+{
+    rects
+        .into_iter()
+        .map(|Rectangle(x, y, w, h)| {
+            let (x, y) = (x as i64, y as i64);
+            (x, y, x + w as i64, y + h as i64)
+        })
+        .fold(None, |bounds, (l, t, r, b)| match bounds {
+            Some((bl, bt, br, bb)) => Some((l.min(bl), t.min(bt), r.max(br), b.max(bb))),
+            None => Some((l, t, r, b)),
+        })
+        .map(|(l, t, r, b)| Rectangle(l as i32, t as i32, (r - l) as u32, (b - t) as u32))
+}
@@ -29,7 +29,7 @@ impl ResizableTallLayout {
         })
     }
 
-    fn tile<U>(
+    pub(crate) fn tile<U>(
         ratio: f32,
         mf: U,
         screen: ScreenDetail,
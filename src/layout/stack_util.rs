@@ -0,0 +1,44 @@
+extern crate wtftw;
+
+use self::wtftw::core::stack::Stack;
+use self::wtftw::window_system::Window;
+
+/// Keep only the windows matching the predicate. If the focused
+/// window is removed, focus moves to the next window below it,
+/// or the one above it if there is none below.
+pub fn filter_stack<F>(stack: &Stack<Window>, f: F) -> Option<Stack<Window>>
+where
+    F: Fn(Window) -> bool,
+{
+    let up: Vec<Window> = stack.up.iter().copied().filter(|&w| f(w)).collect();
+    let down: Vec<Window> = stack.down.iter().copied().filter(|&w| f(w)).collect();
+
+    if f(stack.focus) {
+        Some(Stack::new(stack.focus, up, down))
+    } else if !down.is_empty() {
+        Some(Stack::new(down[0], up, down.into_iter().skip(1).collect()))
+    } else if !up.is_empty() {
+        Some(Stack::new(
+            up[0],
+            up.into_iter().skip(1).collect(),
+            Vec::new(),
+        ))
+    } else {
+        None
+    }
+}
+
+/// Build a stack from the windows in the given order, focusing
+/// `focus` if it is one of them and the first window otherwise.
+pub fn stack_from_windows(windows: &[Window], focus: Window) -> Option<Stack<Window>> {
+    if windows.is_empty() {
+        return None;
+    }
+
+    let index = windows.iter().position(|&w| w == focus).unwrap_or(0);
+    Some(Stack::new(
+        windows[index],
+        windows[..index].iter().rev().copied().collect(),
+        windows[index + 1..].to_vec(),
+    ))
+}
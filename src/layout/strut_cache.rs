@@ -0,0 +1,298 @@
+extern crate wtftw;
+
+use self::wtftw::window_system::Window;
+use self::wtftw::window_system::WindowSystem;
+use super::avoid_struts_layout::{get_dock_strut, Strut};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::rc::Rc;
+
+/// The number of requests a StrutCache sent to the window system
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct StrutQueries {
+    /// Calls to `get_windows`
+    pub windows: usize,
+    /// Calls to `is_dock`
+    pub dock_checks: usize,
+    /// Reads of the strut properties of a dock
+    pub struts: usize,
+}
+
+/// The number of layout passes after which a StrutCache queries
+/// all windows again by default
+pub const DEFAULT_RESCAN: usize = 10;
+
+#[derive(Default)]
+struct CacheState {
+    scanned: bool,
+    docks: BTreeMap<Window, Vec<Strut>>,
+    pending: BTreeSet<Window>,
+    queries: StrutQueries,
+    rescan: Option<usize>,
+    passes: usize,
+}
+
+/// Caches the struts of all dock windows, so an AvoidStrutsLayout
+/// doesn't have to query every window on the display on each pass.
+///
+/// Layouts don't see any X events, so by default all windows are
+/// queried again every `DEFAULT_RESCAN` passes to notice new docks.
+/// Where the window manager's event loop is available, it can call
+/// `window_mapped` on `MapNotify`, `window_unmapped` on
+/// `UnmapNotify` and `DestroyNotify`, and `property_changed` on
+/// `PropertyNotify` for `_NET_WM_STRUT`, `_NET_WM_STRUT_PARTIAL` and
+/// `_NET_WM_WINDOW_TYPE` instead, and turn the rescan off with
+/// `without_rescan`. Copies of a cache share their content, so the
+/// same cache can be handed to several layouts and the event
+/// handling.
+#[derive(Clone)]
+pub struct StrutCache {
+    state: Rc<RefCell<CacheState>>,
+}
+
+impl StrutCache {
+    pub fn new() -> StrutCache {
+        StrutCache {
+            state: Rc::new(RefCell::new(CacheState {
+                rescan: Some(DEFAULT_RESCAN),
+                ..CacheState::default()
+            })),
+        }
+    }
+
+    /// Query all windows again every `passes` layout passes, to
+    /// notice docks without being told about them
+    pub fn with_rescan(self, passes: usize) -> StrutCache {
+        self.state.borrow_mut().rescan = Some(passes.max(1));
+        self
+    }
+
+    /// Only query all windows on the first pass and after
+    /// `invalidate`, relying on the window events to be forwarded
+    pub fn without_rescan(self) -> StrutCache {
+        self.state.borrow_mut().rescan = None;
+        self
+    }
+
+    /// A new window might be a dock, so it is checked on the next pass
+    pub fn window_mapped(&self, window: Window) {
+        self.state.borrow_mut().pending.insert(window);
+    }
+
+    pub fn window_unmapped(&self, window: Window) {
+        let mut state = self.state.borrow_mut();
+        state.docks.remove(&window);
+        state.pending.remove(&window);
+    }
+
+    /// The struts or the window type might have changed, so the
+    /// window is checked again on the next pass
+    pub fn property_changed(&self, window: Window) {
+        self.state.borrow_mut().pending.insert(window);
+    }
+
+    /// Forget all docks and query all windows again on the next pass
+    pub fn invalidate(&self) {
+        let mut state = self.state.borrow_mut();
+        state.scanned = false;
+        state.docks.clear();
+        state.pending.clear();
+    }
+
+    /// The number of requests sent to the window system so far
+    pub fn queries(&self) -> StrutQueries {
+        self.state.borrow().queries
+    }
+
+    /// The struts of all dock windows
+    pub fn struts(&self, window_system: &dyn WindowSystem) -> Vec<Strut> {
+        let mut state = self.state.borrow_mut();
+
+        if let Some(passes) = state.rescan {
+            if state.passes >= passes {
+                state.scanned = false;
+            }
+        }
+        if !state.scanned {
+            state.queries.windows += 1;
+            state.pending = window_system.get_windows().into_iter().collect();
+            state.docks.clear();
+            state.scanned = true;
+            state.passes = 0;
+        }
+        state.passes += 1;
+
+        let pending: Vec<Window> = state.pending.iter().copied().collect();
+        for window in pending {
+            state.queries.dock_checks += 1;
+            if window_system.is_dock(window) {
+                state.queries.struts += 1;
+                state
+                    .docks
+                    .insert(window, get_dock_strut(window_system, window));
+            } else {
+                state.docks.remove(&window);
+            }
+        }
+        state.pending.clear();
+
+        state
+            .docks
+            .values()
+            .flat_map(|s| s.iter().copied())
+            .collect()
+    }
+}
+
+impl Default for StrutCache {
+    fn default() -> StrutCache {
+        StrutCache::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::wtftw::config::GeneralConfig;
+    use super::wtftw::core::stack::Stack;
+    use super::wtftw::layout::{Direction, Layout};
+    use super::wtftw::window_system::{Rectangle, Window, WindowSystem};
+    use super::{StrutCache, StrutQueries, DEFAULT_RESCAN};
+    use crate::layout::avoid_struts_layout::AvoidStrutsLayout;
+    use crate::layout::full_layout::FullLayout;
+    use std::cell::Cell;
+
+    const SCREEN: Rectangle = Rectangle(0, 0, 200, 100);
+    const DOCK: Window = 10;
+
+    /// A display with a single window and a dock at the top,
+    /// counting the requests sent to it
+    #[derive(Default)]
+    struct MockWindowSystem {
+        windows: Cell<usize>,
+        dock_checks: Cell<usize>,
+    }
+
+    impl WindowSystem for MockWindowSystem {
+        fn get_windows(&self) -> Vec<Window> {
+            self.windows.set(self.windows.get() + 1);
+            vec![1, DOCK]
+        }
+
+        fn get_screen_infos(&self) -> Vec<Rectangle> {
+            vec![SCREEN]
+        }
+
+        fn get_class_name(&self, _: Window) -> String {
+            String::new()
+        }
+
+        fn get_window_name(&self, _: Window) -> String {
+            String::new()
+        }
+
+        fn set_window_border_width(&self, _: Window, _: u32) {}
+
+        fn get_window_border_width(&self, _: Window) -> u32 {
+            0
+        }
+
+        fn set_window_border_color(&self, _: Window, _: u32) {}
+
+        fn get_geometry(&self, _: Window) -> Rectangle {
+            SCREEN
+        }
+
+        fn resize_window(&self, _: Window, _: u32, _: u32) {}
+
+        fn is_dock(&self, window: Window) -> bool {
+            self.dock_checks.set(self.dock_checks.get() + 1);
+            window == DOCK
+        }
+
+        fn get_focused_window(&self) -> Window {
+            1
+        }
+
+        fn get_strut(&self, _: Window) -> Option<Vec<u64>> {
+            None
+        }
+
+        fn get_partial_strut(&self, window: Window) -> Option<Vec<u64>> {
+            if window == DOCK {
+                Some(vec![0, 0, 20, 0, 0, 0, 0, 0, 0, 199, 0, 0])
+            } else {
+                None
+            }
+        }
+    }
+
+    fn config() -> GeneralConfig {
+        GeneralConfig {
+            focus_follows_mouse: true,
+            focus_border_color: 0,
+            border_color: 0,
+            border_width: 0,
+            terminal: (String::new(), String::new()),
+            logfile: String::new(),
+            tags: Vec::new(),
+            launcher: String::new(),
+            layout: Box::new(FullLayout),
+        }
+    }
+
+    #[test]
+    fn second_pass_sends_no_requests() {
+        let window_system = MockWindowSystem::default();
+        let cache = StrutCache::new();
+        let mut layout = AvoidStrutsLayout::new(vec![Direction::Up], Box::new(FullLayout))
+            .with_cache(cache.clone());
+        let stack = Some(Stack::from_element(1));
+
+        let first = layout.apply_layout(&window_system, SCREEN, &config(), &stack);
+        let queries = cache.queries();
+        assert_eq!(first, vec![(1, Rectangle(0, 20, 200, 80))]);
+        assert_eq!(
+            queries,
+            StrutQueries {
+                windows: 1,
+                dock_checks: 2,
+                struts: 1,
+            }
+        );
+
+        let second = layout.apply_layout(&window_system, SCREEN, &config(), &stack);
+        assert_eq!(second, first);
+        assert_eq!(cache.queries(), queries);
+        assert_eq!(window_system.windows.get(), 1);
+        assert_eq!(window_system.dock_checks.get(), 2);
+    }
+
+    #[test]
+    fn rescan_queries_all_windows_again() {
+        let window_system = MockWindowSystem::default();
+        let cache = StrutCache::new().with_rescan(2);
+
+        for _ in 0..3 {
+            cache.struts(&window_system);
+        }
+        assert_eq!(window_system.windows.get(), 2);
+        assert_eq!(cache.queries().windows, 2);
+    }
+
+    #[test]
+    fn rescan_is_on_by_default() {
+        let window_system = MockWindowSystem::default();
+        let cache = StrutCache::new();
+
+        for _ in 0..=DEFAULT_RESCAN {
+            cache.struts(&window_system);
+        }
+        assert_eq!(window_system.windows.get(), 2);
+
+        let cache = StrutCache::new().without_rescan();
+        for _ in 0..=DEFAULT_RESCAN {
+            cache.struts(&window_system);
+        }
+        assert_eq!(window_system.windows.get(), 3);
+    }
+}
@@ -0,0 +1,325 @@
+extern crate wtftw;
+
+use self::wtftw::config::GeneralConfig;
+use self::wtftw::core::stack::Stack;
+use self::wtftw::layout::Layout;
+use self::wtftw::layout::LayoutMessage;
+use self::wtftw::window_system::Rectangle;
+use self::wtftw::window_system::Window;
+use self::wtftw::window_system::WindowSystem;
+use super::layout_info::LayoutInfo;
+use super::message_util::same_message;
+use super::stack_util::stack_from_windows;
+
+struct Group {
+    windows: Vec<Window>,
+    active: Window,
+    layout: Box<dyn Layout>,
+}
+
+impl Group {
+    fn copy(&self) -> Group {
+        Group {
+            windows: self.windows.clone(),
+            active: self.active,
+            layout: self.layout.copy(),
+        }
+    }
+}
+
+/// A slot in the stack handed to the outer layout
+/// is either a single window or a whole group
+#[derive(Clone, Copy, PartialEq)]
+enum Slot {
+    Single(Window),
+    Grouped(usize),
+}
+
+/// A layout modifier that allows merging neighbouring windows
+/// into groups. The outer layout sees each group as a single
+/// window and the rectangle it assigns to a group is laid out
+/// by the inner layout, e.g. a FullLayout. Every group gets its
+/// own copy of the inner layout, so their states don't mix.
+///
+/// The group message merges the focused window with the next
+/// window or group, or the previous one if there is no next one.
+/// The ungroup message takes the focused window out of its group
+/// and the next message activates the next window in the focused
+/// group. All other messages go to the outer layout.
+///
+/// Layouts can't move the input focus, so after the next message
+/// the focus stays on the window that was active before, even if
+/// the inner layout hides it, until another window is focused.
+pub struct SubLayout {
+    groups: Vec<Group>,
+    last_focus: Option<Window>,
+    group: LayoutMessage,
+    ungroup: LayoutMessage,
+    next: LayoutMessage,
+    layout: Box<dyn Layout>,
+    inner: Box<dyn Layout>,
+}
+
+impl SubLayout {
+    pub fn boxed_new(
+        group: LayoutMessage,
+        ungroup: LayoutMessage,
+        next: LayoutMessage,
+        layout: Box<dyn Layout>,
+        inner: Box<dyn Layout>,
+    ) -> Box<dyn Layout> {
+        Box::new(SubLayout {
+            groups: Vec::new(),
+            last_focus: None,
+            group,
+            ungroup,
+            next,
+            layout: layout.copy(),
+            inner: inner.copy(),
+        })
+    }
+
+    fn group_of(&self, window: Window) -> Option<usize> {
+        self.groups.iter().position(|g| g.windows.contains(&window))
+    }
+
+    /// Forget windows that are gone and dissolve
+    /// groups with fewer than two windows
+    fn clean(&mut self, windows: &[Window]) {
+        for group in self.groups.iter_mut() {
+            group.windows.retain(|w| windows.contains(w));
+            if !group.windows.contains(&group.active) {
+                if let Some(&w) = group.windows.first() {
+                    group.active = w;
+                }
+            }
+        }
+        self.groups.retain(|g| g.windows.len() > 1);
+    }
+
+    /// The slots seen by the outer layout, in stack order
+    fn slots(&self, windows: &[Window]) -> Vec<Slot> {
+        let mut slots = Vec::new();
+        for &w in windows {
+            let slot = match self.group_of(w) {
+                Some(g) => Slot::Grouped(g),
+                None => Slot::Single(w),
+            };
+            if !slots.contains(&slot) {
+                slots.push(slot);
+            }
+        }
+        slots
+    }
+
+    fn slot_window(&self, slot: Slot) -> Window {
+        match slot {
+            Slot::Single(w) => w,
+            Slot::Grouped(g) => self.groups[g].active,
+        }
+    }
+
+    fn slot_of(&self, window: Window) -> Slot {
+        match self.group_of(window) {
+            Some(g) => Slot::Grouped(g),
+            None => Slot::Single(window),
+        }
+    }
+
+    /// The stack as seen by the outer layout
+    fn outer_stack(&self, stack: &Option<Stack<Window>>) -> Option<Stack<Window>> {
+        stack.as_ref().and_then(|s| {
+            let slots: Vec<Window> = self
+                .slots(&s.integrate())
+                .into_iter()
+                .map(|slot| self.slot_window(slot))
+                .collect();
+            stack_from_windows(&slots, self.slot_window(self.slot_of(s.focus)))
+        })
+    }
+
+    /// Merge the slot of the given window with its neighbour
+    /// `offset` slots away. Returns false if there is no such slot.
+    pub fn merge(&mut self, stack: &Stack<Window>, offset: i32) -> bool {
+        let slots = self.slots(&stack.integrate());
+        let own = self.slot_of(stack.focus);
+        let index = match slots.iter().position(|&s| s == own) {
+            Some(i) => i as i32 + offset,
+            None => return false,
+        };
+        if index < 0 || index as usize >= slots.len() {
+            return false;
+        }
+
+        let windows_of = |slot: Slot| match slot {
+            Slot::Single(w) => vec![w],
+            Slot::Grouped(g) => self.groups[g].windows.clone(),
+        };
+        let other = slots[index as usize];
+        let mut windows = windows_of(own);
+        windows.extend(windows_of(other));
+
+        // The new group keeps the state of the focused group
+        let layout = match (own, other) {
+            (Slot::Grouped(g), _) | (_, Slot::Grouped(g)) => self.groups[g].layout.copy(),
+            _ => self.inner.copy(),
+        };
+
+        let mut groups: Vec<usize> = [own, other]
+            .iter()
+            .filter_map(|&s| match s {
+                Slot::Grouped(g) => Some(g),
+                Slot::Single(_) => None,
+            })
+            .collect();
+        groups.sort_unstable();
+        for g in groups.into_iter().rev() {
+            self.groups.remove(g);
+        }
+
+        // Keep the windows of the new group in stack order
+        let order = stack.integrate();
+        windows.sort_by_key(|w| order.iter().position(|x| x == w));
+        self.groups.push(Group {
+            windows,
+            active: stack.focus,
+            layout,
+        });
+        true
+    }
+
+    /// Take the given window out of its group
+    pub fn ungroup(&mut self, window: Window) {
+        if let Some(g) = self.group_of(window) {
+            self.groups[g].windows.retain(|&w| w != window);
+            if let Some(&w) = self.groups[g].windows.first() {
+                self.groups[g].active = w;
+            }
+            self.groups.retain(|g| g.windows.len() > 1);
+        }
+    }
+
+    /// Activate the next window in the group of the given window
+    pub fn next_in_group(&mut self, window: Window) {
+        if let Some(g) = self.group_of(window) {
+            let group = &mut self.groups[g];
+            let index = group
+                .windows
+                .iter()
+                .position(|&w| w == group.active)
+                .unwrap_or(0);
+            group.active = group.windows[(index + 1) % group.windows.len()];
+        }
+    }
+
+    /// The structured description of the layout and its children
+    pub fn info(&self) -> LayoutInfo {
+        LayoutInfo::new("Sub")
+            .with_child(&*self.layout)
+            .with_child(&*self.inner)
+    }
+}
+
+impl Layout for SubLayout {
+    fn apply_layout(
+        &mut self,
+        window_system: &dyn WindowSystem,
+        screen: Rectangle,
+        config: &GeneralConfig,
+        stack: &Option<Stack<Window>>,
+    ) -> Vec<(Window, Rectangle)> {
+        let s = match *stack {
+            Some(ref s) => s,
+            None => return Vec::new(),
+        };
+        let windows = s.integrate();
+        self.clean(&windows);
+
+        // Focusing a grouped window from outside makes it the
+        // active one of its group
+        if self.last_focus != Some(s.focus) {
+            if let Some(g) = self.group_of(s.focus) {
+                self.groups[g].active = s.focus;
+            }
+            self.last_focus = Some(s.focus);
+        }
+
+        let outer_stack = self.outer_stack(stack);
+        let outer = self
+            .layout
+            .apply_layout(window_system, screen, config, &outer_stack);
+
+        let mut result = Vec::new();
+        for (window, rect) in outer {
+            match self.group_of(window) {
+                Some(g) => {
+                    let group = &mut self.groups[g];
+                    let group_stack = stack_from_windows(&group.windows, group.active);
+                    result.extend(group.layout.apply_layout(
+                        window_system,
+                        rect,
+                        config,
+                        &group_stack,
+                    ));
+                }
+                None => result.push((window, rect)),
+            }
+        }
+        result
+    }
+
+    fn apply_message(
+        &mut self,
+        message: LayoutMessage,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) -> bool {
+        if let Some(s) = stack.as_ref() {
+            if same_message(message, self.group) {
+                if !self.merge(s, 1) {
+                    self.merge(s, -1);
+                }
+                return true;
+            }
+            if same_message(message, self.ungroup) {
+                self.ungroup(s.focus);
+                return true;
+            }
+            if same_message(message, self.next) {
+                self.next_in_group(s.focus);
+                return true;
+            }
+        }
+
+        let outer_stack = self.outer_stack(stack);
+        self.layout
+            .apply_message(message, window_system, &outer_stack, config)
+    }
+
+    fn description(&self) -> String {
+        self.info().to_string()
+    }
+
+    fn copy(&self) -> Box<dyn Layout> {
+        Box::new(SubLayout {
+            groups: self.groups.iter().map(|g| g.copy()).collect(),
+            last_focus: self.last_focus,
+            group: self.group,
+            ungroup: self.ungroup,
+            next: self.next,
+            layout: self.layout.copy(),
+            inner: self.inner.copy(),
+        })
+    }
+
+    fn unhook(
+        &self,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) {
+        let outer_stack = self.outer_stack(stack);
+        self.layout.unhook(window_system, &outer_stack, config);
+    }
+}
@@ -0,0 +1,110 @@
+extern crate wtftw;
+
+use self::wtftw::config::GeneralConfig;
+use self::wtftw::core::stack::Stack;
+use self::wtftw::layout::Layout;
+use self::wtftw::layout::LayoutMessage;
+use self::wtftw::window_system::Rectangle;
+use self::wtftw::window_system::Window;
+use self::wtftw::window_system::WindowSystem;
+use super::layout_info::LayoutInfo;
+use super::message_util::same_message;
+
+/// A layout container holding a primary and an alternate
+/// layout. The toggle message switches between the two, keeping the
+/// state of both, e.g. to zoom into a FullLayout and return
+/// to the exact same BSP tree afterwards. All other messages
+/// go to the active layout.
+pub struct ToggleLayout {
+    pub toggled: bool,
+    toggle: LayoutMessage,
+    primary: Box<dyn Layout>,
+    alternate: Box<dyn Layout>,
+}
+
+impl ToggleLayout {
+    pub fn boxed_new(
+        toggle: LayoutMessage,
+        primary: Box<dyn Layout>,
+        alternate: Box<dyn Layout>,
+    ) -> Box<dyn Layout> {
+        Box::new(ToggleLayout {
+            toggled: false,
+            toggle,
+            primary: primary.copy(),
+            alternate: alternate.copy(),
+        })
+    }
+
+    fn current(&mut self) -> &mut Box<dyn Layout> {
+        if self.toggled {
+            &mut self.alternate
+        } else {
+            &mut self.primary
+        }
+    }
+
+    /// The structured description of the layout and its children
+    pub fn info(&self) -> LayoutInfo {
+        LayoutInfo::new("Toggle")
+            .with_current(if self.toggled { 1 } else { 0 })
+            .with_child(&*self.primary)
+            .with_child(&*self.alternate)
+    }
+}
+
+impl Layout for ToggleLayout {
+    fn apply_layout(
+        &mut self,
+        window_system: &dyn WindowSystem,
+        screen: Rectangle,
+        config: &GeneralConfig,
+        stack: &Option<Stack<Window>>,
+    ) -> Vec<(Window, Rectangle)> {
+        self.current()
+            .apply_layout(window_system, screen, config, stack)
+    }
+
+    fn apply_message(
+        &mut self,
+        message: LayoutMessage,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) -> bool {
+        if same_message(message, self.toggle) {
+            self.current().unhook(window_system, stack, config);
+            self.toggled = !self.toggled;
+            return true;
+        }
+
+        self.current()
+            .apply_message(message, window_system, stack, config)
+    }
+
+    fn description(&self) -> String {
+        self.info().to_string()
+    }
+
+    fn copy(&self) -> Box<dyn Layout> {
+        Box::new(ToggleLayout {
+            toggled: self.toggled,
+            toggle: self.toggle,
+            primary: self.primary.copy(),
+            alternate: self.alternate.copy(),
+        })
+    }
+
+    fn unhook(
+        &self,
+        window_system: &dyn WindowSystem,
+        stack: &Option<Stack<Window>>,
+        config: &GeneralConfig,
+    ) {
+        if self.toggled {
+            self.alternate.unhook(window_system, stack, config);
+        } else {
+            self.primary.unhook(window_system, stack, config);
+        }
+    }
+}
@@ -7,18 +7,70 @@ use self::wtftw::layout::LayoutMessage;
 use self::wtftw::window_system::Rectangle;
 use self::wtftw::window_system::Window;
 use self::wtftw::window_system::WindowSystem;
+use super::border_policy::{default_color, BorderPolicy, WindowContext};
+use super::layout_info::LayoutInfo;
+use super::message_util::same_message;
+use super::rect_util::{covers, with_border};
 
+/// A layout modifier that sets the border width of all windows.
+/// The rectangles of the contained layout are adjusted for the
+/// border width, so the windows tile exactly regardless of
+/// `GeneralConfig::border_width`.
+///
+/// With smart borders, windows get no border while they are the
+/// only window of the contained layout or cover the whole screen.
+/// A BorderPolicy can give windows a different border width and
+/// colour depending on their state.
 pub struct WithBordersLayout {
     border: u32,
+    smart: bool,
+    policy: BorderPolicy,
+    marked: Vec<Window>,
     layout: Box<dyn Layout>,
 }
 
 impl WithBordersLayout {
     pub fn boxed_new(border: u32, layout: Box<dyn Layout>) -> Box<dyn Layout> {
-        Box::new(WithBordersLayout {
+        WithBordersLayout::new(border, layout).boxed()
+    }
+
+    /// Create a new WithBordersLayout to be configured further
+    /// before boxing it, e.g. for smart borders
+    pub fn new(border: u32, layout: Box<dyn Layout>) -> WithBordersLayout {
+        WithBordersLayout {
             border,
+            smart: false,
+            policy: BorderPolicy::new(),
+            marked: Vec::new(),
             layout: layout.copy(),
-        })
+        }
+    }
+
+    pub fn with_smart(mut self, smart: bool) -> WithBordersLayout {
+        self.smart = smart;
+        self
+    }
+
+    /// Style the borders by the given policy, using the border
+    /// width of the layout as the default
+    pub fn with_policy(mut self, policy: BorderPolicy) -> WithBordersLayout {
+        self.policy = policy;
+        self
+    }
+
+    pub fn boxed(self) -> Box<dyn Layout> {
+        Box::new(self)
+    }
+
+    /// The structured description of the layout and its children
+    pub fn info(&self) -> LayoutInfo {
+        let info = LayoutInfo::new("Borders").with_parameter(self.border);
+        let info = if self.smart {
+            info.with_parameter("smart")
+        } else {
+            info
+        };
+        info.with_child(&*self.layout)
     }
 }
 
@@ -30,13 +82,60 @@ impl Layout for WithBordersLayout {
         config: &GeneralConfig,
         stack: &Option<Stack<Window>>,
     ) -> Vec<(Window, Rectangle)> {
-        if let Some(ref s) = *stack {
-            for window in s.integrate().into_iter() {
-                window_system.set_window_border_width(window, self.border);
-            }
+        let layout = self
+            .layout
+            .apply_layout(window_system, screen, config, stack);
+
+        let stack = match *stack {
+            Some(ref s) => s,
+            None => return layout,
+        };
+
+        let windows = stack.integrate();
+        self.marked.retain(|w| windows.contains(w));
+
+        let alone = layout.len() == 1;
+        let focused = window_system.get_focused_window();
+        let borders: Vec<(Window, u32)> = windows
+            .into_iter()
+            .map(|window| {
+                let rect = layout.iter().find(|&&(w, _)| w == window).map(|&(_, r)| r);
+                let context = WindowContext {
+                    window,
+                    focused,
+                    only: alone && rect.is_some(),
+                    marked: self.marked.contains(&window),
+                };
+                let (width, color) =
+                    self.policy
+                        .border(window_system, &context, self.border, config);
+
+                if self.policy.sets_colors() {
+                    window_system.set_window_border_color(window, color);
+                }
+
+                let borderless = match rect {
+                    Some(r) => self.smart && (alone || covers(r, screen)),
+                    None => false,
+                };
+                (window, if borderless { 0 } else { width })
+            })
+            .collect();
+
+        for &(window, border) in borders.iter() {
+            window_system.set_window_border_width(window, border);
         }
-        self.layout
-            .apply_layout(window_system, screen, config, stack)
+
+        layout
+            .into_iter()
+            .map(|(window, rect)| {
+                let border = borders
+                    .iter()
+                    .find(|&&(w, _)| w == window)
+                    .map_or(self.border, |&(_, b)| b);
+                (window, with_border(rect, border, config.border_width))
+            })
+            .collect()
     }
 
     fn apply_message(
@@ -46,17 +145,35 @@ impl Layout for WithBordersLayout {
         stack: &Option<Stack<Window>>,
         config: &GeneralConfig,
     ) -> bool {
+        let mark = match self.policy.mark {
+            Some(m) => same_message(m, message),
+            None => false,
+        };
+        if mark {
+            if let Some(ref s) = *stack {
+                if self.marked.contains(&s.focus) {
+                    self.marked.retain(|&w| w != s.focus);
+                } else {
+                    self.marked.push(s.focus);
+                }
+            }
+            return true;
+        }
+
         self.layout
             .apply_message(message, window_system, stack, config)
     }
 
     fn description(&self) -> String {
-        self.layout.description()
+        self.info().to_string()
     }
 
     fn copy(&self) -> Box<dyn Layout> {
         Box::new(WithBordersLayout {
             border: self.border,
+            smart: self.smart,
+            policy: self.policy.clone(),
+            marked: self.marked.clone(),
             layout: self.layout.copy(),
         })
     }
@@ -68,14 +185,20 @@ impl Layout for WithBordersLayout {
         config: &GeneralConfig,
     ) {
         if let Some(ref s) = *stack {
+            let focused = window_system.get_focused_window();
+            // Keep the outer size of the windows until they are
+            // arranged by the next layout
             for window in s.integrate().into_iter() {
-                window_system.set_window_border_width(window, config.border_width);
+                let border = window_system.get_window_border_width(window);
                 let Rectangle(_, _, w, h) = window_system.get_geometry(window);
-                window_system.resize_window(
-                    window,
-                    w + 2 * config.border_width,
-                    h + 2 * config.border_width,
-                );
+                let Rectangle(_, _, w, h) =
+                    with_border(Rectangle(0, 0, w, h), config.border_width, border);
+                window_system.set_window_border_width(window, config.border_width);
+                window_system.resize_window(window, w, h);
+                if self.policy.sets_colors() {
+                    let color = default_color(window, focused, config);
+                    window_system.set_window_border_color(window, color);
+                }
             }
         }
     }
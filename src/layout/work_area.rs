@@ -0,0 +1,251 @@
+extern crate wtftw;
+
+use self::wtftw::layout::Direction;
+use self::wtftw::window_system::Rectangle;
+use self::wtftw::window_system::WindowSystem;
+use super::avoid_struts_layout::{get_dock_strut, Strut};
+
+/// The root window, i.e. the bounding box of all screens. Struts
+/// are given relative to the edges of the root window.
+pub fn root_rect(window_system: &dyn WindowSystem, screen: Rectangle) -> Rectangle {
+    bounding_box(
+        window_system
+            .get_screen_infos()
+            .into_iter()
+            .chain(Some(screen)),
+    )
+}
+
+/// The struts of all dock windows on the display
+pub fn dock_struts(window_system: &dyn WindowSystem) -> Vec<Strut> {
+    window_system
+        .get_windows()
+        .into_iter()
+        .filter(|&w| window_system.is_dock(w))
+        .flat_map(|x| get_dock_strut(window_system, x).into_iter())
+        .collect()
+}
+
+/// The part of the screen that isn't reserved by any of the struts,
+/// i.e. where windows can be placed without overlapping a dock. The
+/// root window is needed to resolve the struts, see `root_rect`.
+/// Struts on the same edge overlap, so only the largest one counts.
+pub fn work_area(screen: Rectangle, root: Rectangle, struts: &[Strut]) -> Rectangle {
+    let reserved = |direction: Direction| {
+        struts
+            .iter()
+            .filter(|&&Strut(d, _, _, _)| d == direction)
+            .map(|&s| reserved_space(s, root, screen))
+            .max()
+            .unwrap_or(0)
+    };
+
+    let (top, bottom) = (reserved(Direction::Up), reserved(Direction::Down));
+    let (left, right) = (reserved(Direction::Left), reserved(Direction::Right));
+    let Rectangle(x, y, w, h) = screen;
+    Rectangle(
+        x + left as i32,
+        y + top as i32,
+        w.saturating_sub(left + right),
+        h.saturating_sub(top + bottom),
+    )
+}
+
+/// The work area of every screen, in the order of `get_screen_infos`
+pub fn work_areas(window_system: &dyn WindowSystem, struts: &[Strut]) -> Vec<Rectangle> {
+    let screens = window_system.get_screen_infos();
+    let root = bounding_box(screens.iter().copied());
+    screens
+        .into_iter()
+        .map(|screen| work_area(screen, root, struts))
+        .collect()
+}
+
+/// The bounding box of the work areas of all screens. This is what
+/// `_NET_WORKAREA` holds, as it only has room for a single rectangle.
+pub fn work_area_union(areas: &[Rectangle]) -> Option<Rectangle> {
+    let left = areas.iter().map(|&Rectangle(x, _, _, _)| x as i64).min()?;
+    let top = areas.iter().map(|&Rectangle(_, y, _, _)| y as i64).min()?;
+    let right = areas
+        .iter()
+        .map(|&Rectangle(x, _, w, _)| x as i64 + w as i64)
+        .max()?;
+    let bottom = areas
+        .iter()
+        .map(|&Rectangle(_, y, _, h)| y as i64 + h as i64)
+        .max()?;
+
+    Some(Rectangle(
+        left as i32,
+        top as i32,
+        (right - left) as u32,
+        (bottom - top) as u32,
+    ))
+}
+
+/// The area that is part of the work areas of all screens, e.g. for
+/// cloned screens. None if the work areas don't overlap.
+pub fn work_area_intersection(areas: &[Rectangle]) -> Option<Rectangle> {
+    let left = areas.iter().map(|&Rectangle(x, _, _, _)| x as i64).max()?;
+    let top = areas.iter().map(|&Rectangle(_, y, _, _)| y as i64).max()?;
+    let right = areas
+        .iter()
+        .map(|&Rectangle(x, _, w, _)| x as i64 + w as i64)
+        .min()?;
+    let bottom = areas
+        .iter()
+        .map(|&Rectangle(_, y, _, h)| y as i64 + h as i64)
+        .min()?;
+
+    if right <= left || bottom <= top {
+        return None;
+    }
+    Some(Rectangle(
+        left as i32,
+        top as i32,
+        (right - left) as u32,
+        (bottom - top) as u32,
+    ))
+}
+
+/// The value of the `_NET_WORKAREA` property: x, y, width and height
+/// of the work area, repeated for every desktop
+pub fn net_workarea(area: Rectangle, desktops: usize) -> Vec<u64> {
+    let Rectangle(x, y, w, h) = area;
+    let values = [x.max(0) as u64, y.max(0) as u64, w as u64, h as u64];
+    values.iter().cycle().take(4 * desktops).copied().collect()
+}
+
+/// The rectangle from the origin to the bottom right corner
+/// of the rectangle reaching furthest
+fn bounding_box<I>(rects: I) -> Rectangle
+where
+    I: Iterator<Item = Rectangle>,
+{
+    let (w, h) = rects.fold((0, 0), |(w, h), Rectangle(x, y, sw, sh)| {
+        (w.max(x as i64 + sw as i64), h.max(y as i64 + sh as i64))
+    });
+    Rectangle(0, 0, w as u32, h as u32)
+}
+
+/// The number of pixels the strut reserves on the screen. Only the
+/// part of the strut that lies within the screen is considered, so
+/// a dock on one monitor doesn't affect the others.
+fn reserved_space(strut: Strut, root: Rectangle, screen: Rectangle) -> u32 {
+    fn clamp(x: u64) -> i64 {
+        x.min(i64::MAX as u64) as i64
+    }
+
+    let Strut(d, n, start, end) = strut;
+    let (n, start, end) = (clamp(n), clamp(start), clamp(end));
+    let Rectangle(_, _, rw, rh) = root;
+    let Rectangle(x, y, w, h) = screen;
+    let (sx, sy, sw, sh) = (x as i64, y as i64, w as i64, h as i64);
+
+    // The strut covers the range from start to end (inclusive)
+    // along the edge of the root window
+    let (edge_start, edge_end, size) = match d {
+        Direction::Left | Direction::Right => (sy, sy + sh, sw),
+        Direction::Up | Direction::Down => (sx, sx + sw, sh),
+    };
+    if end < edge_start || start >= edge_end {
+        return 0;
+    }
+
+    let reserved = match d {
+        Direction::Left => n - sx,
+        Direction::Right => sx + sw - (rw as i64 - n),
+        Direction::Up => n - sy,
+        Direction::Down => sy + sh - (rh as i64 - n),
+    };
+    reserved.max(0).min(size) as u32
+}
+
+#[cfg(test)]
+mod test {
+    use super::wtftw::window_system::{Rectangle, Window, WindowSystem};
+    use super::{dock_struts, work_areas};
+    use crate::layout::strut_cache::StrutCache;
+
+    const LEFT: Rectangle = Rectangle(0, 0, 200, 100);
+    const RIGHT: Rectangle = Rectangle(200, 0, 200, 100);
+    const PARTIAL_DOCK: Window = 10;
+    const LEGACY_DOCK: Window = 11;
+
+    /// Two monitors next to each other, with a dock setting a
+    /// partial strut at the top of the right one and a dock
+    /// setting a legacy strut at the top of the left one
+    struct MockWindowSystem;
+
+    impl WindowSystem for MockWindowSystem {
+        fn get_windows(&self) -> Vec<Window> {
+            vec![1, PARTIAL_DOCK, LEGACY_DOCK]
+        }
+
+        fn get_screen_infos(&self) -> Vec<Rectangle> {
+            vec![LEFT, RIGHT]
+        }
+
+        fn get_class_name(&self, _: Window) -> String {
+            String::new()
+        }
+
+        fn get_window_name(&self, _: Window) -> String {
+            String::new()
+        }
+
+        fn set_window_border_width(&self, _: Window, _: u32) {}
+
+        fn get_window_border_width(&self, _: Window) -> u32 {
+            0
+        }
+
+        fn set_window_border_color(&self, _: Window, _: u32) {}
+
+        fn get_geometry(&self, window: Window) -> Rectangle {
+            match window {
+                PARTIAL_DOCK => Rectangle(200, 0, 200, 20),
+                LEGACY_DOCK => Rectangle(0, 0, 200, 30),
+                _ => LEFT,
+            }
+        }
+
+        fn resize_window(&self, _: Window, _: u32, _: u32) {}
+
+        fn is_dock(&self, window: Window) -> bool {
+            window == PARTIAL_DOCK || window == LEGACY_DOCK
+        }
+
+        fn get_focused_window(&self) -> Window {
+            1
+        }
+
+        fn get_strut(&self, window: Window) -> Option<Vec<u64>> {
+            if window == LEGACY_DOCK {
+                Some(vec![0, 0, 30, 0])
+            } else {
+                None
+            }
+        }
+
+        fn get_partial_strut(&self, window: Window) -> Option<Vec<u64>> {
+            if window == PARTIAL_DOCK {
+                Some(vec![0, 0, 20, 0, 0, 0, 0, 0, 200, 399, 0, 0])
+            } else {
+                None
+            }
+        }
+    }
+
+    #[test]
+    fn struts_stay_on_the_monitor_of_their_dock() {
+        let window_system = MockWindowSystem;
+        let expected = vec![Rectangle(0, 30, 200, 70), Rectangle(200, 20, 200, 80)];
+
+        let struts = dock_struts(&window_system);
+        assert_eq!(work_areas(&window_system, &struts), expected);
+
+        let struts = StrutCache::new().struts(&window_system);
+        assert_eq!(work_areas(&window_system, &struts), expected);
+    }
+}